@@ -0,0 +1,42 @@
+//! Generates the list of languages from the resource files in `src/lang`,
+//! so a new language needs nothing but a new `<locale>.ftl` file.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+const LANG_DIR: &str = "src/lang";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", LANG_DIR);
+
+    let mut locales: Vec<String> = fs::read_dir(LANG_DIR)
+        .expect("Failed to read the language directory")
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "ftl" {
+                return None;
+            }
+            Some(path.file_stem()?.to_str()?.to_string())
+        })
+        .collect();
+    locales.sort();
+
+    let entries: String = locales
+        .iter()
+        .map(|locale| {
+            format!(
+                "    (\"{0}\", include_str!(concat!(env!(\"CARGO_MANIFEST_DIR\"), \"/{1}/{0}.ftl\"))),\n",
+                locale, LANG_DIR
+            )
+        })
+        .collect();
+    let code = format!(
+        "/// Locales and resources of all files in `{}`, sorted by locale.\n\
+         pub const LANGUAGES: &[(&str, &str)] = &[\n{}];\n",
+        LANG_DIR, entries
+    );
+    let out_file =
+        Path::new(&env::var("OUT_DIR").expect("OUT_DIR is not set")).join("languages.rs");
+    fs::write(out_file, code).expect("Failed to write the list of languages");
+}
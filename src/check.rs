@@ -0,0 +1,108 @@
+//! Checking compares the guesses with the unique solution of the puzzle,
+//! so it also finds wrong fields, which do not break any rule yet.
+
+use crate::control::CellPos;
+use binoxxo::field::{Board, Field};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum CheckState {
+    #[default]
+    Hidden,
+    /// all filled fields match the solution
+    Correct,
+    /// wrong fields with the value they had when checked
+    Wrong(Vec<(CellPos, Field)>),
+    /// the puzzle has no unique solution to compare with
+    Unavailable,
+}
+
+impl CheckState {
+    /// Compares the filled fields of `board` with `solution`.
+    pub fn of(board: &Board, solution: &Board) -> CheckState {
+        let size = board.get_size();
+        let wrong: Vec<(CellPos, Field)> = (0..size)
+            .flat_map(|row| (0..size).map(move |col| CellPos { col, row }))
+            .map(|pos| (pos, board.get(pos.col, pos.row)))
+            .filter(|(pos, field)| {
+                Field::Empty != *field && solution.get(pos.col, pos.row) != *field
+            })
+            .collect();
+        if wrong.is_empty() {
+            CheckState::Correct
+        } else {
+            CheckState::Wrong(wrong)
+        }
+    }
+
+    pub fn is_wrong_at(&self, col: usize, row: usize) -> bool {
+        matches!(self, CheckState::Wrong(wrong) if wrong.iter().any(|(pos, _)| *pos == CellPos { col, row }))
+    }
+
+    /// Returns how many wrong fields of `self` were not already wrong in `previous`.
+    pub fn new_mistakes(&self, previous: &CheckState) -> usize {
+        match (self, previous) {
+            (CheckState::Wrong(wrong), CheckState::Wrong(before)) => {
+                wrong.iter().filter(|field| !before.contains(field)).count()
+            }
+            (CheckState::Wrong(wrong), _) => wrong.len(),
+            _ => 0,
+        }
+    }
+
+    /// Forgets the result after `board` changed, but keeps the fields,
+    /// which still have the wrong value.
+    pub fn board_changed(&mut self, board: &Board) {
+        *self = match std::mem::take(self) {
+            CheckState::Wrong(wrong) => {
+                let still_wrong: Vec<(CellPos, Field)> = wrong
+                    .into_iter()
+                    .filter(|(pos, field)| board.get(pos.col, pos.row) == *field)
+                    .collect();
+                if still_wrong.is_empty() {
+                    CheckState::Hidden
+                } else {
+                    CheckState::Wrong(still_wrong)
+                }
+            }
+            _ => CheckState::Hidden,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    const SOLUTION: &str = "X O O X\nO X X O\nX X O O\nO O X X";
+
+    #[test]
+    fn find_wrong_fields() {
+        let solution = Board::from_str(SOLUTION).unwrap();
+        // the X in the third column breaks no rule, but is wrong
+        let board = Board::from_str("X O X _\n_ _ _ _\n_ _ _ _\n_ _ _ _").unwrap();
+        let check = CheckState::of(&board, &solution);
+        assert!(check.is_wrong_at(2, 0));
+        assert!(!check.is_wrong_at(1, 0));
+        assert_eq!(1, check.new_mistakes(&CheckState::Hidden));
+        assert_eq!(0, check.new_mistakes(&check));
+
+        let correct = Board::from_str("X O O _\n_ _ _ _\n_ _ _ _\n_ _ _ _").unwrap();
+        assert_eq!(CheckState::Correct, CheckState::of(&correct, &solution));
+    }
+
+    #[test]
+    fn keep_fields_still_wrong() {
+        let solution = Board::from_str(SOLUTION).unwrap();
+        let mut board = Board::from_str("X O X _\n_ _ _ _\n_ _ _ X\n_ _ _ _").unwrap();
+        let mut check = CheckState::of(&board, &solution);
+        board.set(2, 0, Field::O);
+        check.board_changed(&board);
+        assert!(!check.is_wrong_at(2, 0));
+        assert!(check.is_wrong_at(3, 2));
+
+        board.clear(3, 2);
+        check.board_changed(&board);
+        assert_eq!(CheckState::Hidden, check);
+    }
+}
@@ -0,0 +1,160 @@
+//! Finds the rules broken on a board and the fields breaking them.
+
+use binoxxo::field::{Board, Field};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Conflict {
+    /// three equal fields next to each other
+    Triple,
+    /// more `X` or more `O` in a row or column than half its length
+    Imbalance,
+    /// two complete rows or two complete columns are equal
+    Duplicate,
+}
+
+impl Conflict {
+    /// Returns the CSS class highlighting the fields.
+    pub fn class(&self) -> &'static str {
+        match self {
+            Conflict::Triple => "triple",
+            Conflict::Imbalance => "imbalance",
+            Conflict::Duplicate => "duplicate",
+        }
+    }
+
+    /// Returns the id of the translated explanation.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            Conflict::Triple => "conflict-triple",
+            Conflict::Imbalance => "conflict-imbalance",
+            Conflict::Duplicate => "conflict-duplicate",
+        }
+    }
+}
+
+/// Conflicts of every field of a board.
+pub struct Conflicts {
+    size: usize,
+    fields: Vec<Vec<Conflict>>,
+}
+
+impl Conflicts {
+    /// Returns no conflicts for a board of `size`.
+    pub fn none(size: usize) -> Conflicts {
+        Conflicts {
+            size,
+            fields: vec![Vec::new(); size * size],
+        }
+    }
+
+    pub fn of(board: &Board) -> Conflicts {
+        let size = board.get_size();
+        let mut conflicts = Conflicts::none(size);
+        // every line as positions of its fields, rows first
+        let lines: Vec<Vec<(usize, usize)>> = (0..size)
+            .map(|row| (0..size).map(|col| (col, row)).collect())
+            .chain((0..size).map(|col| (0..size).map(|row| (col, row)).collect()))
+            .collect();
+        let field = |pos: &(usize, usize)| board.get(pos.0, pos.1);
+        let is_full =
+            |line: &Vec<(usize, usize)>| line.iter().all(|pos| Field::Empty != field(pos));
+
+        for line in &lines {
+            for triple in line.windows(3) {
+                let first = field(&triple[0]);
+                if Field::Empty != first && triple.iter().all(|pos| field(pos) == first) {
+                    conflicts.add(triple, Conflict::Triple);
+                }
+            }
+            let too_many =
+                |value: Field| line.iter().filter(|pos| field(pos) == value).count() > size / 2;
+            if too_many(Field::X) || too_many(Field::O) {
+                conflicts.add(line, Conflict::Imbalance);
+            }
+        }
+
+        // rows are compared with rows, columns with columns
+        for half in lines.chunks(size) {
+            for (i, line) in half.iter().enumerate() {
+                let equals = |other: &Vec<(usize, usize)>| {
+                    line.iter().zip(other).all(|(a, b)| field(a) == field(b))
+                };
+                if is_full(line)
+                    && half
+                        .iter()
+                        .enumerate()
+                        .any(|(j, other)| i != j && is_full(other) && equals(other))
+                {
+                    conflicts.add(line, Conflict::Duplicate);
+                }
+            }
+        }
+        conflicts
+    }
+
+    fn add(&mut self, positions: &[(usize, usize)], conflict: Conflict) {
+        for (col, row) in positions {
+            let kinds = &mut self.fields[row * self.size + col];
+            if !kinds.contains(&conflict) {
+                kinds.push(conflict);
+            }
+        }
+    }
+
+    /// Returns the conflicts of the field at `col` and `row`.
+    pub fn at(&self, col: usize, row: usize) -> &[Conflict] {
+        &self.fields[row * self.size + col]
+    }
+
+    /// Returns the number of fields with a conflict.
+    pub fn count(&self) -> usize {
+        self.fields.iter().filter(|kinds| !kinds.is_empty()).count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    fn conflicts(board: &str) -> Conflicts {
+        Conflicts::of(&Board::from_str(board).unwrap())
+    }
+
+    #[test]
+    fn find_triple_and_imbalance() {
+        let conflicts = conflicts(
+            "
+            O _ _ _
+            X _ _ _
+            X _ _ _
+            X _ _ _",
+        );
+        assert_eq!(&[Conflict::Imbalance], conflicts.at(0, 0));
+        assert_eq!(&[Conflict::Triple, Conflict::Imbalance], conflicts.at(0, 1));
+        assert_eq!(&[] as &[Conflict], conflicts.at(1, 1));
+        assert_eq!(4, conflicts.count());
+    }
+
+    #[test]
+    fn find_duplicate_rows() {
+        let conflicts = conflicts(
+            "
+            X O X O
+            O X _ _
+            X O X O
+            _ _ _ _",
+        );
+        assert_eq!(&[Conflict::Duplicate], conflicts.at(3, 0));
+        assert_eq!(&[Conflict::Duplicate], conflicts.at(1, 2));
+        assert_eq!(&[] as &[Conflict], conflicts.at(0, 1));
+        assert_eq!(8, conflicts.count());
+    }
+
+    #[test]
+    fn none_has_no_conflicts() {
+        let conflicts = Conflicts::none(6);
+        assert_eq!(&[] as &[Conflict], conflicts.at(5, 5));
+        assert_eq!(0, conflicts.count());
+    }
+}
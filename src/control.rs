@@ -1,25 +1,100 @@
-use crate::model::{Difficulty, Model};
-use binoxxo::field::Field;
+use crate::check::CheckState;
+use crate::generator::daily_seed;
+use crate::hint::{find_hint, HintState};
+use crate::history::{CellChange, History};
+use crate::keyboard::{move_cursor, KeyAction};
+use crate::model::{
+    set_field, CustomGame, Difficulty, GameResult, Generation, Language, Marks, Model, Notes,
+};
+use crate::rating::Rating;
+use crate::share::{decode_puzzle, encode_puzzle};
+use crate::solutions::{count_solutions, unique_solution, SolutionCount};
+use crate::text_format::{format_puzzle, parse_puzzle, ImportError};
+use crate::timer::Timer;
+use binoxxo::field::{Board, Field};
+use binoxxo::rules::is_board_valid;
 use seed::browser::web_storage::LocalStorage;
 use seed::prelude::*;
+use seed::JsFuture;
+use serde_derive::{Deserialize, Serialize};
 
 pub const DIFFICULTY_STORAGE: &str = "Binoxxo-Difficulty";
+pub const CUSTOM_GAME_STORAGE: &str = "Binoxxo-Custom-Game";
 pub const LANGUAGE_STORAGE: &str = "Binoxxo-Language";
 pub const HELPER_STORAGE: &str = "Binoxxo-Helper";
+/// Bump the version, whenever `SavedGame` changes incompatibly.
+pub const GAME_STORAGE: &str = "Binoxxo-Game-v1";
+pub const RECORDS_STORAGE: &str = "Binoxxo-Records";
+pub const GAME_LOG_STORAGE: &str = "Binoxxo-Game-Log";
 
-#[derive(Clone, Copy, Debug)]
+/// Difficulty of the daily puzzle, which is the same for every player.
+const DAILY_DIFFICULTY: Difficulty = Difficulty::Medium;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct CellPos {
     pub col: usize,
     pub row: usize,
 }
 
-#[derive(Clone, Copy, Debug)]
+/// Fields to set to the solution, once the player confirms.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Reveal {
+    Cell(CellPos),
+    Solution,
+}
+
+#[derive(Clone, Debug)]
 pub enum Message {
     NewGame(Difficulty),
+    NewDailyGame,
+    NewCustomGame,
+    GenerationStep,
+    CancelGeneration,
+    ToggleCustomGame,
+    SetCustomSize(usize),
+    SetCustomDifficulty(Difficulty),
+    NewEditor,
+    PlayDesign,
     Toggle(CellPos),
+    ToggleNoteMode,
     Clear,
-    ToggleLanguage,
+    Undo,
+    Redo,
+    Hint,
+    Check,
+    AskReveal(Reveal),
+    ConfirmReveal,
+    CancelReveal,
+    SetLanguage(Language),
     ToggleHelper,
+    ShareProgress,
+    LoadUrl(Url),
+    Tick,
+    VisibilityChanged,
+    ToggleStatistics,
+    ToggleDiagnostics,
+    ToggleTransfer,
+    ImportTextChanged(String),
+    ImportPuzzle,
+    ImportFile(web_sys::File),
+    ImportFileLoaded(String),
+    CopyPuzzle,
+    Copied(bool),
+    Key(KeyAction),
+}
+
+pub fn now() -> f64 {
+    js_sys::Date::now()
+}
+
+/// Returns the languages preferred by the user, the most preferred first.
+pub fn browser_languages() -> Vec<String> {
+    seed::window()
+        .navigator()
+        .languages()
+        .iter()
+        .filter_map(|language| language.as_string())
+        .collect()
 }
 
 fn next_field(field: Field) -> Field {
@@ -31,38 +106,425 @@ fn next_field(field: Field) -> Field {
 }
 
 fn toggle_field(model: &mut Model, pos: &CellPos) {
-    let field = next_field(model.board.get(pos.col, pos.row));
-    if field == Field::Empty {
-        model.board.clear(pos.col, pos.row);
-    } else {
-        model.board.set(pos.col, pos.row, field);
+    // clicking selects the field, e.g. to reveal it
+    model.selected = Some(*pos);
+    // a visible cursor follows the mouse
+    if model.cursor.is_some() {
+        model.cursor = Some(*pos);
+    }
+    if model.note_mode && !model.editing {
+        change_marks(model, pos, Marks::next);
+        return;
+    }
+    let after = next_field(model.board.get(pos.col, pos.row));
+    change_field(model, pos, after);
+}
+
+/// Changes the pencil marks of an empty, editable field.
+fn change_marks(model: &mut Model, pos: &CellPos, change: impl FnOnce(Marks) -> Marks) {
+    if !model.editable.is_editable(pos.col, pos.row)
+        || Field::Empty != model.board.get(pos.col, pos.row)
+    {
+        return;
+    }
+    let marks = model.notes.get(pos.col, pos.row);
+    model.notes.set(pos.col, pos.row, change(marks));
+}
+
+fn change_field(model: &mut Model, pos: &CellPos, after: Field) {
+    let before = model.board.get(pos.col, pos.row);
+    if !model.editable.is_editable(pos.col, pos.row)
+        || model.revealed.contains(pos)
+        || before == after
+    {
+        return;
     }
+    set_field(&mut model.board, pos.col, pos.row, after);
+    if Field::Empty != after {
+        model.notes.set(pos.col, pos.row, Marks::default());
+    }
+    board_changed(model);
+    model.history.record(vec![CellChange {
+        col: pos.col,
+        row: pos.row,
+        before,
+        after,
+    }]);
 }
 
-fn new_game(model: &mut Model, difficulty: Difficulty) {
+fn new_game(model: &mut Model, difficulty: Difficulty, orders: &mut impl Orders<Message>) {
     seed::log!(model.board.to_string());
     seed::log!(format!("Store {} = {}", DIFFICULTY_STORAGE, difficulty));
     LocalStorage::insert(DIFFICULTY_STORAGE, &difficulty).ok();
 
-    let new_model = Model::new(difficulty, model.helper, model.language);
+    start_generation(model, Generation::new(difficulty), orders);
+}
+
+fn new_custom_game(model: &mut Model, orders: &mut impl Orders<Message>) {
+    let custom = model.custom;
+    seed::log!(format!("Store {} = {:?}", CUSTOM_GAME_STORAGE, custom));
+    LocalStorage::insert(CUSTOM_GAME_STORAGE, &custom).ok();
+
+    model.show_custom = false;
+    start_generation(model, Generation::custom(custom), orders);
+}
+
+/// Generates the next puzzle in steps, the current game stays playable meanwhile.
+pub fn start_generation(
+    model: &mut Model,
+    generation: Generation,
+    orders: &mut impl Orders<Message>,
+) {
+    // replacing a running generation drops and so aborts its scheduled step
+    model.generation = Some(generation);
+    schedule_generation_step(model, orders);
+}
+
+fn schedule_generation_step(model: &mut Model, orders: &mut impl Orders<Message>) {
+    if let Some(generation) = &mut model.generation {
+        // the timeout lets the browser render and handle input between the steps
+        generation.next_step =
+            Some(orders.perform_cmd_with_handle(cmds::timeout(0, || Message::GenerationStep)));
+    }
+}
+
+fn generation_step(model: &mut Model, orders: &mut impl Orders<Message>) {
+    let board = match model.generation.as_mut().map(Generation::step) {
+        Some(Some(board)) => board,
+        Some(None) => return schedule_generation_step(model, orders),
+        // cancelled
+        None => return,
+    };
+    let generation = model.generation.take().expect("generation is running");
+    seed::log!(format!(
+        "Generated puzzle after {} attempts",
+        generation.attempts()
+    ));
+    let new_model = Model::generated(&generation, board, model.helper, model.language);
+    seed::log!(format!("Rating: {:?}", new_model.rating));
+    start_game(model, new_model);
+    show_puzzle_url(model, false);
+}
+
+/// Stops the generation, unless there is no game to go back to.
+fn cancel_generation(model: &mut Model) {
+    if !model.waiting {
+        model.generation = None;
+    }
+}
+
+fn set_custom_size(model: &mut Model, size: usize) {
+    let custom = CustomGame {
+        size,
+        ..model.custom
+    };
+    if custom.is_valid() {
+        model.custom = custom;
+    }
+}
+
+fn daily_game(model: &mut Model, orders: &mut impl Orders<Message>) {
+    let today = js_sys::Date::new_0();
+    let (year, month, day) = (
+        today.get_full_year(),
+        today.get_month() + 1,
+        today.get_date(),
+    );
+    let date = format!("{:04}-{:02}-{:02}", year, month, day);
+    if model.daily.as_ref() == Some(&date) {
+        // keep the progress on today's puzzle
+        return;
+    }
+
+    let seed = daily_seed(year, month, day);
+    seed::log!(format!("Daily puzzle {} with seed {}", date, seed));
+    start_generation(
+        model,
+        Generation::daily(DAILY_DIFFICULTY, seed, date),
+        orders,
+    );
+}
+
+fn start_game(model: &mut Model, new_model: Model) {
+    reset_game(model, new_model);
+    model.timer.start(model.now);
+    log_game_start(model);
+}
+
+/// Replaces the game by `new_model` without starting the timer.
+/// A running generation is cancelled, as its puzzle would replace the new game.
+fn reset_game(model: &mut Model, new_model: Model) {
+    model.generation = None;
+    model.waiting = new_model.waiting;
     model.board = new_model.board;
     model.difficulty = new_model.difficulty;
     model.editable = new_model.editable;
+    model.notes = new_model.notes;
+    model.daily = new_model.daily;
+    model.is_custom = new_model.is_custom;
+    model.rating = new_model.rating;
+    model.editing = new_model.editing;
+    model.solution_count = new_model.solution_count;
+    model.hint = HintState::Hidden;
+    model.check = CheckState::Hidden;
+    model.mistakes = 0;
+    model.pending_reveal = None;
+    model.revealed = new_model.revealed;
+    model.history = History::default();
+    model.result = None;
+    model.timer = Timer::default();
+    let size = model.get_size();
+    model.cursor = model.cursor.filter(|pos| pos.col < size && pos.row < size);
+    model.selected = model.cursor;
+}
+
+fn start_editor(model: &mut Model) {
+    let size = model.custom.size;
+    seed::log!(format!("Design puzzle of size {}", size));
+    model.show_custom = false;
+    let new_model = Model::new_editor(size, model.helper, model.language);
+    reset_game(model, new_model);
+    show_puzzle_url(model, false);
+}
+
+/// Updates the state depending on the board after the player changed it.
+fn board_changed(model: &mut Model) {
+    model.hint = HintState::Hidden;
+    model.check.board_changed(&model.board);
+    if model.editing {
+        check_design(model);
+    }
+}
+
+/// Counts the solutions and rates the puzzle in the editor.
+pub fn check_design(model: &mut Model) {
+    model.solution_count = count_solutions(&model.board);
+    model.rating = Rating::of(&model.board);
+}
+
+/// Leaves the editor and plays the designed puzzle.
+fn play_design(model: &mut Model) {
+    if SolutionCount::NoSolution == model.solution_count {
+        return;
+    }
+    let givens = model.board.clone();
+    seed::log!(format!("Play designed puzzle:\n{}", givens.to_string()));
+    start_puzzle(model, &givens, givens.clone());
+    show_puzzle_url(model, false);
+}
+
+fn log_game_start(model: &mut Model) {
+    let size = model.get_size();
+    model.game_log.log_start(model.difficulty, size);
+    LocalStorage::insert(GAME_LOG_STORAGE, &model.game_log).ok();
+}
+
+/// Puts the encoded puzzle into the URL fragment, so that the URL can be shared.
+pub fn show_puzzle_url(model: &Model, with_guesses: bool) {
+    let givens = model.puzzle_givens();
+    let board = if with_guesses {
+        Some(&model.board)
+    } else {
+        None
+    };
+    Url::current()
+        .set_hash(encode_puzzle(&givens, board))
+        .go_and_replace();
+}
+
+/// Starts the puzzle encoded in the fragment of `url`.
+/// Keeps the current game, if it already is the linked puzzle without any guesses.
+/// Malformed or tampered fragments are logged and ignored.
+/// Returns whether a new game was started.
+pub fn load_shared_puzzle(model: &mut Model, url: &Url) -> bool {
+    let fragment = match url.hash() {
+        Some(fragment) if !fragment.is_empty() => fragment,
+        _ => return false,
+    };
+    let puzzle = match decode_puzzle(fragment) {
+        Some(puzzle) => puzzle,
+        None => {
+            seed::log!(format!("Ignore invalid puzzle link: {}", fragment));
+            return false;
+        }
+    };
+    let current_givens = model.puzzle_givens();
+    if current_givens == puzzle.givens && puzzle.givens == puzzle.board {
+        return false;
+    }
+    let started = start_puzzle(model, &puzzle.givens, puzzle.board);
+    if started {
+        seed::log!(format!("Load puzzle from link: {}", fragment));
+    } else {
+        seed::log!(format!("Ignore invalid puzzle link: {}", fragment));
+    }
+    started
+}
+
+/// Starts a game of a puzzle with `givens`, which is already partially played on `board`.
+/// Designed, imported and linked puzzles are custom games, which do not count for the records.
+/// Returns `false` if `board` does not contain all `givens` or the puzzle has no solution.
+fn start_puzzle(model: &mut Model, givens: &Board, board: Board) -> bool {
+    if SolutionCount::NoSolution == count_solutions(givens) {
+        return false;
+    }
+    // puzzles of non-standard sizes get the difficulty of their rating
+    let difficulty = Difficulty::from_size(givens.get_size())
+        .or_else(|| Rating::of(givens).map(|rating| rating.difficulty()))
+        .unwrap_or_default();
+    match Model::with_board(difficulty, model.helper, model.language, givens, board) {
+        Some(mut new_model) => {
+            new_model.is_custom = true;
+            start_game(model, new_model);
+            true
+        }
+        None => false,
+    }
+}
+
+fn toggle_transfer(model: &mut Model) {
+    model.transfer.visible = !model.transfer.visible;
+    model.transfer.error = None;
+    model.transfer.copied = None;
+}
+
+fn import_puzzle(model: &mut Model) {
+    match parse_puzzle(&model.transfer.import_text) {
+        Ok(givens) => {
+            seed::log!(format!("Import puzzle:\n{}", givens.to_string()));
+            if start_puzzle(model, &givens, givens.clone()) {
+                show_puzzle_url(model, false);
+                model.transfer = Default::default();
+            } else {
+                // keep the dialog open, the current game goes on
+                model.transfer.error = Some(ImportError::NoSolution);
+            }
+        }
+        Err(error) => model.transfer.error = Some(error),
+    }
+}
+
+fn import_file(file: web_sys::File, orders: &mut impl Orders<Message>) {
+    orders.perform_cmd(async move {
+        let text = JsFuture::from(file.text()).await.ok()?.as_string()?;
+        Some(Message::ImportFileLoaded(text))
+    });
+}
+
+/// Writes `text` to the clipboard.
+/// `navigator.clipboard` is looked up dynamically, because web-sys binds it only as unstable API.
+fn write_clipboard(text: &str) -> Option<js_sys::Promise> {
+    let clipboard = js_sys::Reflect::get(&seed::window().navigator(), &"clipboard".into()).ok()?;
+    let write_text: js_sys::Function = js_sys::Reflect::get(&clipboard, &"writeText".into())
+        .ok()?
+        .dyn_into()
+        .ok()?;
+    write_text
+        .call1(&clipboard, &text.into())
+        .ok()?
+        .dyn_into()
+        .ok()
+}
+
+fn copy_puzzle(model: &Model, orders: &mut impl Orders<Message>) {
+    let promise = write_clipboard(&format_puzzle(&model.puzzle_givens()));
+    orders.perform_cmd(async move {
+        let copied = match promise {
+            Some(promise) => JsFuture::from(promise).await.is_ok(),
+            None => false,
+        };
+        Message::Copied(copied)
+    });
 }
 
 fn clear_board(model: &mut Model) {
     let size = model.get_size();
+    let mut changes = Vec::new();
     for col in 0..size {
         for row in 0..size {
-            if model.editable.is_editable(col, row) && Field::Empty != model.board.get(col, row) {
+            let before = model.board.get(col, row);
+            if model.editable.is_editable(col, row)
+                && !model.revealed.contains(&CellPos { col, row })
+                && Field::Empty != before
+            {
                 model.board.clear(col, row);
+                changes.push(CellChange {
+                    col,
+                    row,
+                    before,
+                    after: Field::Empty,
+                });
             }
         }
     }
+    model.notes = Notes::new(size);
+    board_changed(model);
+    model.history.record(changes);
+}
+
+fn undo_move(model: &mut Model) {
+    model.history.undo(&mut model.board);
+    board_changed(model);
+}
+
+fn redo_move(model: &mut Model) {
+    model.history.redo(&mut model.board);
+    board_changed(model);
+}
+
+fn show_hint(model: &mut Model) {
+    let solution = unique_solution(&model.puzzle_givens());
+    model.hint = find_hint(&model.board, solution.as_ref());
+}
+
+/// Compares the board with the solution and counts the newly found wrong fields.
+fn check_board(model: &mut Model) {
+    let check = match unique_solution(&model.puzzle_givens()) {
+        Some(solution) => CheckState::of(&model.board, &solution),
+        None => CheckState::Unavailable,
+    };
+    model.mistakes += check.new_mistakes(&model.check);
+    model.check = check;
+}
+
+/// Sets the fields of the confirmed reveal to the solution.
+/// Revealed fields are locked and the game no longer counts for the records.
+fn reveal_fields(model: &mut Model) {
+    let reveal = match model.pending_reveal.take() {
+        Some(reveal) => reveal,
+        None => return,
+    };
+    let solution = match unique_solution(&model.puzzle_givens()) {
+        Some(solution) => solution,
+        None => {
+            model.check = CheckState::Unavailable;
+            return;
+        }
+    };
+    let size = model.get_size();
+    let positions = match reveal {
+        Reveal::Cell(pos) => vec![pos],
+        Reveal::Solution => (0..size)
+            .flat_map(|row| (0..size).map(move |col| CellPos { col, row }))
+            .collect(),
+    };
+    for pos in positions {
+        let field = solution.get(pos.col, pos.row);
+        if model.editable.is_editable(pos.col, pos.row)
+            && model.board.get(pos.col, pos.row) != field
+        {
+            set_field(&mut model.board, pos.col, pos.row, field);
+            model.notes.set(pos.col, pos.row, Marks::default());
+            model.revealed.push(pos);
+        }
+    }
+    // undoing older moves could overwrite revealed fields
+    model.history = History::default();
+    board_changed(model);
 }
 
-fn change_language(model: &mut Model) {
-    model.language = model.language.next();
+fn change_language(model: &mut Model, language: Language) {
+    model.language = language;
     seed::log!(format!(
         "Store {} = {}",
         LANGUAGE_STORAGE,
@@ -81,14 +543,129 @@ fn change_helper(model: &mut Model) {
     LocalStorage::insert(HELPER_STORAGE, &model.helper).ok();
 }
 
-pub fn update(message: Message, model: &mut Model, _: &mut impl Orders<Message>) {
+/// Plays with the keyboard on the cell under the cursor.
+/// Keys are ignored while an overlay covers the board.
+fn key_pressed(model: &mut Model, action: KeyAction) {
+    if model.result.is_some() || model.show_statistics || model.transfer.visible {
+        return;
+    }
+    match (action, model.cursor) {
+        (KeyAction::Move(direction), cursor) => {
+            model.cursor = Some(move_cursor(cursor, direction, model.get_size()));
+            model.selected = model.cursor;
+        }
+        (KeyAction::Set(field), Some(pos)) if model.note_mode && !model.editing => {
+            change_marks(model, &pos, |marks| marks.toggle(field))
+        }
+        (KeyAction::Clear, Some(pos)) if model.note_mode && !model.editing => {
+            change_marks(model, &pos, |_| Marks::default())
+        }
+        (KeyAction::Set(field), Some(pos)) => change_field(model, &pos, field),
+        (KeyAction::Clear, Some(pos)) => change_field(model, &pos, Field::Empty),
+        (KeyAction::Undo, _) => undo_move(model),
+        (KeyAction::ToggleNotes, _) => model.note_mode = !model.note_mode,
+        // no cell selected yet
+        (_, None) => (),
+    }
+}
+
+/// Pauses the timer and stores the game, while the page is hidden.
+fn change_visibility(model: &mut Model) {
+    if seed::document().hidden() {
+        model.timer.pause(model.now);
+        // the page may be closed, keep the time played since the last change
+        save_game(model);
+    } else if model.result.is_none() && !model.editing {
+        model.timer.start(model.now);
+    }
+}
+
+/// Stops the timer and records the time, when the board becomes valid.
+fn finish_game(model: &mut Model) {
+    if model.editing || model.result.is_some() || !is_board_valid(&model.board) {
+        return;
+    }
+    model.timer.pause(model.now);
+    let time_ms = model.timer.elapsed_ms(model.now) as u64;
+    let assisted = !model.revealed.is_empty();
+    let new_record = !model.is_custom && !assisted && model.records.add(model.difficulty, time_ms);
+    seed::log!(format!("Store {}", RECORDS_STORAGE));
+    LocalStorage::insert(RECORDS_STORAGE, &model.records).ok();
+    let size = model.get_size();
+    model
+        .game_log
+        .log_win(model.difficulty, size, time_ms, assisted);
+    LocalStorage::insert(GAME_LOG_STORAGE, &model.game_log).ok();
+    model.result = Some(GameResult {
+        time_ms,
+        new_record,
+    });
+}
+
+fn save_game(model: &Model) {
+    if model.waiting {
+        return;
+    }
+    LocalStorage::insert(GAME_STORAGE, &model.to_saved(model.now)).ok();
+}
+
+pub fn update(message: Message, model: &mut Model, orders: &mut impl Orders<Message>) {
+    model.now = now();
+    if let Message::Tick = message {
+        return;
+    }
     seed::log!(format!("Got {:?}", message));
+    let before = model.to_saved(model.now);
 
     match message {
         Message::Toggle(pos) => toggle_field(model, &pos),
-        Message::NewGame(difficulty) => new_game(model, difficulty),
+        Message::NewGame(difficulty) => new_game(model, difficulty, orders),
+        Message::NewDailyGame => daily_game(model, orders),
+        Message::NewCustomGame => new_custom_game(model, orders),
+        Message::GenerationStep => generation_step(model, orders),
+        Message::CancelGeneration => cancel_generation(model),
+        Message::ToggleCustomGame => model.show_custom = !model.show_custom,
+        Message::SetCustomSize(size) => set_custom_size(model, size),
+        Message::SetCustomDifficulty(difficulty) => model.custom.difficulty = difficulty,
+        Message::NewEditor => start_editor(model),
+        Message::PlayDesign => play_design(model),
+        Message::ToggleNoteMode => model.note_mode = !model.note_mode,
         Message::Clear => clear_board(model),
-        Message::ToggleLanguage => change_language(model),
+        Message::Undo => undo_move(model),
+        Message::Redo => redo_move(model),
+        Message::Hint => show_hint(model),
+        Message::Check => check_board(model),
+        Message::AskReveal(reveal) => model.pending_reveal = Some(reveal),
+        Message::ConfirmReveal => reveal_fields(model),
+        Message::CancelReveal => model.pending_reveal = None,
+        Message::SetLanguage(language) => change_language(model, language),
         Message::ToggleHelper => change_helper(model),
+        Message::ShareProgress => show_puzzle_url(model, true),
+        Message::LoadUrl(url) => {
+            load_shared_puzzle(model, &url);
+        }
+        Message::Tick => (),
+        Message::VisibilityChanged => change_visibility(model),
+        Message::ToggleStatistics => model.show_statistics = !model.show_statistics,
+        Message::ToggleDiagnostics => model.show_diagnostics = !model.show_diagnostics,
+        Message::Key(action) => key_pressed(model, action),
+        Message::ToggleTransfer => toggle_transfer(model),
+        Message::ImportTextChanged(text) => {
+            model.transfer.import_text = text;
+            model.transfer.error = None;
+        }
+        Message::ImportPuzzle => import_puzzle(model),
+        Message::ImportFile(file) => import_file(file, orders),
+        Message::ImportFileLoaded(text) => {
+            model.transfer.import_text = text;
+            import_puzzle(model);
+        }
+        Message::CopyPuzzle => copy_puzzle(model, orders),
+        Message::Copied(copied) => model.transfer.copied = Some(copied),
+    }
+
+    finish_game(model);
+    if model.to_saved(model.now) != before {
+        save_game(model);
     }
 }
@@ -0,0 +1,150 @@
+//! Seeded puzzle generator.
+//!
+//! Follows the brute force algorithm of `binoxxo::bruteforce::create_puzzle_board`,
+//! but draws all random decisions from a seeded random number generator.
+//! Hence, the same seed and parameters always result in the same puzzle.
+
+use binoxxo::bruteforce::possible_move::{calc_possible_moves, PossibleMove};
+use binoxxo::field::{Board, Field};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+#[derive(Clone, Copy, Debug)]
+struct Move {
+    x: usize,
+    y: usize,
+    was_random: bool,
+}
+
+fn select_next_move(
+    possible_moves: &[PossibleMove],
+    rng: &mut ChaCha8Rng,
+) -> Option<(Move, Field)> {
+    if possible_moves.is_empty() || possible_moves.contains(&PossibleMove::NoMove) {
+        return None;
+    }
+
+    let single_options: Vec<&PossibleMove> = possible_moves
+        .iter()
+        .filter(|m| matches!(m, PossibleMove::OneMove(_, _, _)))
+        .collect();
+    if let Some(PossibleMove::OneMove(x, y, field)) = single_options.choose(rng).copied() {
+        let m = Move {
+            x: *x,
+            y: *y,
+            was_random: false,
+        };
+        return Some((m, *field));
+    }
+
+    match possible_moves.choose(rng) {
+        Some(PossibleMove::TwoMoves(x, y)) => {
+            let field = if rng.gen() { Field::X } else { Field::O };
+            let m = Move {
+                x: *x,
+                y: *y,
+                was_random: true,
+            };
+            Some((m, field))
+        }
+        _ => None,
+    }
+}
+
+fn build_full_board(size: usize, rng: &mut ChaCha8Rng) -> Option<(Board, Vec<Move>)> {
+    let max_tries = size * size * 100;
+    let mut board = Board::new(size);
+    let mut moves: Vec<Move> = Vec::new();
+
+    for _ in 0..max_tries {
+        if moves.len() == size * size {
+            return Some((board, moves));
+        }
+        let possible_moves = calc_possible_moves(&mut board);
+        if let Some((m, field)) = select_next_move(&possible_moves, rng) {
+            board.set(m.x, m.y, field);
+            moves.push(m);
+        } else {
+            // dead end: take back a random number of moves
+            let number_of_moves = rng.gen_range(1..moves.len().max(2));
+            for _ in 0..number_of_moves.min(moves.len()) {
+                let m = moves.pop().unwrap();
+                board.clear(m.x, m.y);
+            }
+        }
+    }
+
+    if moves.len() == size * size {
+        Some((board, moves))
+    } else {
+        None
+    }
+}
+
+/// Returns a binoxxo puzzle board of side length `size` for `seed`.
+/// `guesses` tunes the difficulty like for `create_puzzle_board`.
+///
+/// # Panics
+///
+/// Panics if `size` is odd or zero, or if no board was found
+/// (see `binoxxo::bruteforce::create_puzzle_board`).
+pub fn create_seeded_puzzle_board(seed: u64, size: usize, guesses: usize) -> Board {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let (mut board, mut moves) = build_full_board(size, &mut rng)
+        .unwrap_or_else(|| panic!("No board found for size {} and seed {}", size, seed));
+
+    let mut guesses = guesses;
+    while let Some(m) = moves.pop() {
+        if !m.was_random {
+            board.clear(m.x, m.y);
+        } else if 0 == guesses {
+            break;
+        } else {
+            guesses -= 1;
+        }
+    }
+
+    board
+}
+
+/// Returns the seed of the daily puzzle for the given date.
+pub fn daily_seed(year: u32, month: u32, day: u32) -> u64 {
+    u64::from(year) * 10_000 + u64::from(month) * 100 + u64::from(day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use binoxxo::bruteforce::possible_move::calc_possible_moves;
+
+    #[test]
+    fn same_seed_same_board() {
+        for seed in 0..5 {
+            assert_eq!(
+                create_seeded_puzzle_board(seed, 8, 10),
+                create_seeded_puzzle_board(seed, 8, 10)
+            );
+        }
+    }
+
+    #[test]
+    fn different_seeds_different_boards() {
+        assert_ne!(
+            create_seeded_puzzle_board(1, 10, 15),
+            create_seeded_puzzle_board(2, 10, 15)
+        );
+    }
+
+    #[test]
+    fn puzzle_can_be_continued() {
+        let mut board = create_seeded_puzzle_board(42, 6, 5);
+        assert!(!calc_possible_moves(&mut board).contains(&PossibleMove::NoMove));
+    }
+
+    #[test]
+    fn daily_seed_per_date() {
+        assert_eq!(20261018, daily_seed(2026, 10, 18));
+        assert_ne!(daily_seed(2026, 10, 18), daily_seed(2026, 10, 19));
+    }
+}
@@ -0,0 +1,183 @@
+//! Hints show the player the next step of the logical solver
+//! together with the technique explaining it.
+
+use crate::model::set_field;
+use crate::solver::{next_step, Step, Technique};
+use binoxxo::field::{Board, Field};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum HintState {
+    #[default]
+    Hidden,
+    Found(Step),
+    NotFound,
+}
+
+impl HintState {
+    pub fn is_hint_at(&self, col: usize, row: usize) -> bool {
+        matches!(self, HintState::Found(step) if step.col == col && step.row == row)
+    }
+}
+
+/// Returns a field, which follows from the rules and the fields on `board`.
+/// Fields differing from the `solution` are left out, so wrong guesses never lead to a hint.
+/// Simpler techniques are preferred over harder ones.
+pub fn find_hint(board: &Board, solution: Option<&Board>) -> HintState {
+    let mut correct = board.clone();
+    if let Some(solution) = solution {
+        let size = board.get_size();
+        for col in 0..size {
+            for row in 0..size {
+                let field = board.get(col, row);
+                if Field::Empty != field && solution.get(col, row) != field {
+                    set_field(&mut correct, col, row, Field::Empty);
+                }
+            }
+        }
+    }
+    match next_step(&correct) {
+        Some(step) => HintState::Found(step),
+        None => HintState::NotFound,
+    }
+}
+
+/// Returns the id of the translated explanation of `technique`.
+pub fn message_id(technique: Technique) -> &'static str {
+    match technique {
+        Technique::Pair => "hint-pair",
+        Technique::NoTriple => "hint-no-triple",
+        Technique::Balance => "hint-balance",
+        Technique::Uniqueness => "hint-uniqueness",
+        Technique::LineAnalysis => "hint-line-analysis",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    fn hint(board: &str) -> HintState {
+        find_hint(&Board::from_str(board).unwrap(), None)
+    }
+
+    #[test]
+    fn pair_rule() {
+        let found = hint(
+            "
+            _ X X _
+            _ _ _ _
+            _ _ _ _
+            _ _ _ _",
+        );
+        assert_eq!(
+            HintState::Found(Step {
+                col: 0,
+                row: 0,
+                field: Field::O,
+                technique: Technique::Pair
+            }),
+            found
+        );
+    }
+
+    #[test]
+    fn no_triple_rule() {
+        let found = hint(
+            "
+            _ _ _ _
+            O _ _ _
+            _ _ _ _
+            O _ _ _",
+        );
+        assert_eq!(
+            HintState::Found(Step {
+                col: 0,
+                row: 2,
+                field: Field::X,
+                technique: Technique::NoTriple
+            }),
+            found
+        );
+    }
+
+    #[test]
+    fn balance_rule() {
+        let found = hint(
+            "
+            _ _ _ _
+            X _ _ X
+            _ _ _ _
+            _ _ _ _",
+        );
+        assert_eq!(
+            HintState::Found(Step {
+                col: 1,
+                row: 1,
+                field: Field::O,
+                technique: Technique::Balance
+            }),
+            found
+        );
+    }
+
+    #[test]
+    fn uniqueness_rule() {
+        let found = hint(
+            "
+            X O O X O X
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            X O O X _ _",
+        );
+        assert_eq!(
+            HintState::Found(Step {
+                col: 4,
+                row: 5,
+                field: Field::X,
+                technique: Technique::Uniqueness
+            }),
+            found
+        );
+    }
+
+    #[test]
+    fn no_hint_on_empty_board() {
+        assert_eq!(HintState::NotFound, find_hint(&Board::new(6), None));
+    }
+
+    #[test]
+    fn ignore_wrong_guesses() {
+        let solution = Board::from_str("X O X O\nX X O O\nO X O X\nO O X X").unwrap();
+        // the X in the second column is wrong, by the pair rule it would make the first field an O
+        let board = Board::from_str("_ X X _\n_ _ _ _\n_ _ _ _\n_ _ _ _").unwrap();
+        assert!(find_hint(&board, None).is_hint_at(0, 0));
+        assert_eq!(HintState::NotFound, find_hint(&board, Some(&solution)));
+
+        let board = Board::from_str("_ X X _\n_ _ _ _\n_ _ _ _\nO O _ _").unwrap();
+        assert_eq!(
+            HintState::Found(Step {
+                col: 2,
+                row: 3,
+                field: Field::X,
+                technique: Technique::Pair
+            }),
+            find_hint(&board, Some(&solution))
+        );
+    }
+
+    #[test]
+    fn hint_position() {
+        let found = hint(
+            "
+            _ X X _
+            _ _ _ _
+            _ _ _ _
+            _ _ _ _",
+        );
+        assert!(found.is_hint_at(0, 0));
+        assert!(!found.is_hint_at(3, 0));
+    }
+}
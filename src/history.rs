@@ -0,0 +1,139 @@
+use crate::model::set_field;
+use binoxxo::field::{Board, Field};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CellChange {
+    pub col: usize,
+    pub row: usize,
+    pub before: Field,
+    pub after: Field,
+}
+
+/// Undo and redo stacks of board moves.
+/// Every entry holds all cells changed by a single message,
+/// e.g. one cell for a toggle or many cells for clearing the board.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    undo: Vec<Vec<CellChange>>,
+    redo: Vec<Vec<CellChange>>,
+}
+
+impl History {
+    /// Records a new move and drops everything that could have been redone.
+    pub fn record(&mut self, changes: Vec<CellChange>) {
+        if !changes.is_empty() {
+            self.undo.push(changes);
+            self.redo.clear();
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Rolls back the last move on `board`.
+    /// Returns the rolled back cells or `None` if there is nothing to undo.
+    pub fn undo(&mut self, board: &mut Board) -> Option<Vec<CellChange>> {
+        let changes = self.undo.pop()?;
+        for change in changes.iter().rev() {
+            set_field(board, change.col, change.row, change.before);
+        }
+        self.redo.push(changes.clone());
+        Some(changes)
+    }
+
+    /// Reapplies the last undone move on `board`.
+    /// Returns the reapplied cells or `None` if there is nothing to redo.
+    pub fn redo(&mut self, board: &mut Board) -> Option<Vec<CellChange>> {
+        let changes = self.redo.pop()?;
+        for change in changes.iter() {
+            set_field(board, change.col, change.row, change.after);
+        }
+        self.undo.push(changes.clone());
+        Some(changes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn toggle(board: &mut Board, history: &mut History, col: usize, row: usize, field: Field) {
+        let before = board.get(col, row);
+        set_field(board, col, row, field);
+        history.record(vec![CellChange {
+            col,
+            row,
+            before,
+            after: field,
+        }]);
+    }
+
+    #[test]
+    fn undo_and_redo_single_move() {
+        let mut board = Board::new(4);
+        let mut history = History::default();
+        assert!(!history.can_undo());
+
+        toggle(&mut board, &mut history, 1, 2, Field::X);
+        assert!(history.can_undo());
+        assert!(!history.can_redo());
+
+        assert!(history.undo(&mut board).is_some());
+        assert_eq!(Field::Empty, board.get(1, 2));
+        assert!(!history.can_undo());
+        assert!(history.can_redo());
+
+        assert!(history.redo(&mut board).is_some());
+        assert_eq!(Field::X, board.get(1, 2));
+        assert!(history.redo(&mut board).is_none());
+    }
+
+    #[test]
+    fn undo_restores_all_cells_of_one_move() {
+        let mut board = Board::new(4);
+        let mut history = History::default();
+        toggle(&mut board, &mut history, 0, 0, Field::X);
+        toggle(&mut board, &mut history, 3, 1, Field::O);
+
+        // clear both cells in a single move
+        let changes = vec![
+            CellChange {
+                col: 0,
+                row: 0,
+                before: Field::X,
+                after: Field::Empty,
+            },
+            CellChange {
+                col: 3,
+                row: 1,
+                before: Field::O,
+                after: Field::Empty,
+            },
+        ];
+        for change in changes.iter() {
+            set_field(&mut board, change.col, change.row, change.after);
+        }
+        history.record(changes);
+
+        history.undo(&mut board);
+        assert_eq!(Field::X, board.get(0, 0));
+        assert_eq!(Field::O, board.get(3, 1));
+    }
+
+    #[test]
+    fn new_move_discards_redo() {
+        let mut board = Board::new(4);
+        let mut history = History::default();
+        toggle(&mut board, &mut history, 0, 0, Field::X);
+        history.undo(&mut board);
+        assert!(history.can_redo());
+
+        toggle(&mut board, &mut history, 1, 1, Field::O);
+        assert!(!history.can_redo());
+    }
+}
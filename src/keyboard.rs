@@ -0,0 +1,97 @@
+//! Maps keys to game actions, so that the game can be played without a mouse.
+
+use crate::control::CellPos;
+use binoxxo::field::Field;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyAction {
+    Move(Direction),
+    Set(Field),
+    Clear,
+    Undo,
+    ToggleNotes,
+}
+
+/// Returns the action bound to `key`, which is a `KeyboardEvent.key` value.
+pub fn key_action(key: &str) -> Option<KeyAction> {
+    match key {
+        "ArrowUp" | "w" | "W" => Some(KeyAction::Move(Direction::Up)),
+        "ArrowDown" | "s" | "S" => Some(KeyAction::Move(Direction::Down)),
+        "ArrowLeft" | "a" | "A" => Some(KeyAction::Move(Direction::Left)),
+        "ArrowRight" | "d" | "D" => Some(KeyAction::Move(Direction::Right)),
+        "x" | "X" => Some(KeyAction::Set(Field::X)),
+        "o" | "O" => Some(KeyAction::Set(Field::O)),
+        " " | "Backspace" | "Delete" => Some(KeyAction::Clear),
+        "u" | "U" => Some(KeyAction::Undo),
+        "n" | "N" => Some(KeyAction::ToggleNotes),
+        _ => None,
+    }
+}
+
+/// Moves `cursor` one cell into `direction`, but not beyond the border of the board.
+/// A hidden cursor appears in the top left cell.
+pub fn move_cursor(cursor: Option<CellPos>, direction: Direction, size: usize) -> CellPos {
+    let CellPos { col, row } = match cursor {
+        Some(pos) => pos,
+        None => return CellPos { col: 0, row: 0 },
+    };
+    let last = size - 1;
+    match direction {
+        Direction::Up => CellPos {
+            col,
+            row: row.saturating_sub(1),
+        },
+        Direction::Down => CellPos {
+            col,
+            row: (row + 1).min(last),
+        },
+        Direction::Left => CellPos {
+            col: col.saturating_sub(1),
+            row,
+        },
+        Direction::Right => CellPos {
+            col: (col + 1).min(last),
+            row,
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn map_keys() {
+        assert_eq!(Some(KeyAction::Move(Direction::Up)), key_action("ArrowUp"));
+        assert_eq!(Some(KeyAction::Move(Direction::Left)), key_action("a"));
+        assert_eq!(Some(KeyAction::Set(Field::O)), key_action("O"));
+        assert_eq!(Some(KeyAction::Clear), key_action(" "));
+        assert_eq!(Some(KeyAction::Undo), key_action("u"));
+        assert_eq!(Some(KeyAction::ToggleNotes), key_action("n"));
+        assert_eq!(None, key_action("Enter"));
+    }
+
+    #[test]
+    fn cursor_stays_on_board() {
+        let start = move_cursor(None, Direction::Down, 6);
+        assert_eq!(CellPos { col: 0, row: 0 }, start);
+        assert_eq!(start, move_cursor(Some(start), Direction::Up, 6));
+        assert_eq!(start, move_cursor(Some(start), Direction::Left, 6));
+
+        let corner = CellPos { col: 5, row: 5 };
+        assert_eq!(corner, move_cursor(Some(corner), Direction::Right, 6));
+        assert_eq!(corner, move_cursor(Some(corner), Direction::Down, 6));
+        assert_eq!(
+            CellPos { col: 4, row: 5 },
+            move_cursor(Some(corner), Direction::Left, 6)
+        );
+    }
+}
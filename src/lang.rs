@@ -1,20 +1,32 @@
 use elsa::FrozenMap;
 use fluent_bundle::{FluentBundle, FluentResource};
 use lazy_static::lazy_static;
+use std::cell::RefCell;
 use std::collections::HashMap;
 use unic_langid::LanguageIdentifier;
 
+include!(concat!(env!("OUT_DIR"), "/languages.rs"));
+
+/// Messages missing in a locale are taken from this one.
+pub const FALLBACK_LOCALE: &str = "en-US";
+
 lazy_static! {
-    static ref LANG_RESOURCES: HashMap<&'static str, &'static str> = {
-        let mut m = HashMap::new();
-        m.insert("de-DE", include_str!("lang/de-DE"));
-        m.insert("en-US", include_str!("lang/en-US"));
-        m
-    };
+    static ref LANG_RESOURCES: HashMap<&'static str, &'static str> =
+        LANGUAGES.iter().copied().collect();
+}
+
+/// A message which could not be translated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub locale: String,
+    pub id: String,
+    pub problem: String,
 }
 
 pub struct ResourceManager {
     resources: FrozenMap<String, Box<FluentResource>>,
+    /// problems found while rendering, shown in the diagnostics panel
+    diagnostics: RefCell<Vec<Diagnostic>>,
 }
 
 impl std::fmt::Debug for ResourceManager {
@@ -27,36 +39,76 @@ impl ResourceManager {
     pub fn new() -> Self {
         ResourceManager {
             resources: FrozenMap::new(),
+            diagnostics: RefCell::new(Vec::new()),
         }
     }
 
-    fn get_resource(&self, locale: &str) -> &FluentResource {
+    /// Records a translation problem, each problem is recorded once.
+    pub fn report(&self, diagnostic: Diagnostic) {
+        let mut diagnostics = self.diagnostics.borrow_mut();
+        if !diagnostics.contains(&diagnostic) {
+            diagnostics.push(diagnostic);
+        }
+    }
+
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics.borrow().clone()
+    }
+
+    /// Returns the resource of `locale`, `None` for unknown locales.
+    fn get_resource(&self, locale: &str) -> Option<&FluentResource> {
         if let Some(res) = self.resources.get(locale) {
-            res
+            Some(res)
         } else {
-            let raw_resource = LANG_RESOURCES.get(locale).unwrap();
+            let raw_resource = LANG_RESOURCES.get(locale)?;
             let res = match FluentResource::try_new((*raw_resource).to_string()) {
                 Ok(res) => res,
                 Err((res, _err)) => res,
             };
-            self.resources.insert(locale.to_string(), Box::new(res))
+            Some(self.resources.insert(locale.to_string(), Box::new(res)))
         }
     }
 
+    /// Returns the bundle of `locale_str`, which falls back to `FALLBACK_LOCALE`
+    /// for unknown locales and for messages missing in the locale.
     pub fn get_bundle(&self, locale_str: &str) -> FluentBundle<&FluentResource> {
-        let loc: LanguageIdentifier = locale_str.parse().expect("invalid locale string");
-        let mut bundle = FluentBundle::new(vec![loc]);
-        let res = self.get_resource(locale_str);
-        bundle
-            .add_resource(res)
-            .expect("Failed to add FTL resource");
-        bundle
+        let fallback = self
+            .get_resource(FALLBACK_LOCALE)
+            .expect("fallback locale is missing");
+        let resource = self.get_resource(locale_str);
+        let locale = resource.and_then(|_| locale_str.parse().ok());
+        build_bundle(locale, resource, fallback)
+    }
+}
+
+/// Builds a bundle with all messages of `fallback`, overridden by the messages of `resource`.
+fn build_bundle<'a>(
+    locale: Option<LanguageIdentifier>,
+    resource: Option<&'a FluentResource>,
+    fallback: &'a FluentResource,
+) -> FluentBundle<&'a FluentResource> {
+    let fallback_locale: LanguageIdentifier = FALLBACK_LOCALE
+        .parse()
+        .expect("invalid fallback locale string");
+    let locales = match locale {
+        Some(locale) => vec![locale, fallback_locale],
+        None => vec![fallback_locale],
+    };
+    let mut bundle = FluentBundle::new(locales);
+    bundle
+        .add_resource(fallback)
+        .expect("Failed to add FTL resource");
+    if let Some(resource) = resource {
+        bundle.add_resource_overriding(resource);
     }
+    bundle
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use fluent_syntax::ast;
+    use std::collections::HashSet;
 
     #[test]
     fn trans_en() {
@@ -91,4 +143,70 @@ mod test {
         assert_eq!("Leicht", b.format_pattern(value, None, &mut errors));
         assert!(errors.is_empty());
     }
+
+    fn format(bundle: &FluentBundle<&FluentResource>, id: &str) -> String {
+        let mut errors = vec![];
+        let value = bundle
+            .get_message(id)
+            .expect("Failed to retrieve the message")
+            .value()
+            .expect("Failed to retrieve the value of the message");
+        let text = bundle.format_pattern(value, None, &mut errors).to_string();
+        assert!(errors.is_empty());
+        text
+    }
+
+    #[test]
+    fn unknown_locale_falls_back() {
+        let rmgr = ResourceManager::new();
+        let b = rmgr.get_bundle("fr-FR");
+        assert_eq!("Easy", format(&b, "difficulty-Easy"));
+    }
+
+    #[test]
+    fn missing_message_falls_back() {
+        let fallback = FluentResource::try_new("hello = Hello\nbye = Bye\n".to_string()).unwrap();
+        let partial = FluentResource::try_new("hello = Hallo\n".to_string()).unwrap();
+        let b = build_bundle(Some("de-DE".parse().unwrap()), Some(&partial), &fallback);
+        assert_eq!("Hallo", format(&b, "hello"));
+        assert_eq!("Bye", format(&b, "bye"));
+    }
+
+    /// Returns the ids of the messages of `locale`, which must parse without errors.
+    fn message_ids(locale: &str, ftl: &str) -> HashSet<String> {
+        let resource = match FluentResource::try_new(ftl.to_string()) {
+            Ok(resource) => resource,
+            Err((_, errors)) => panic!("{} does not parse: {:?}", locale, errors),
+        };
+        resource
+            .entries()
+            .filter_map(|entry| match entry {
+                ast::Entry::Message(message) => Some(message.id.name.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn locales_have_the_messages_of_the_fallback() {
+        let fallback = message_ids(FALLBACK_LOCALE, LANG_RESOURCES[FALLBACK_LOCALE]);
+        for (locale, ftl) in LANGUAGES {
+            let ids = message_ids(locale, ftl);
+            let missing: Vec<_> = fallback.difference(&ids).collect();
+            assert!(
+                missing.is_empty(),
+                "{:?} are missing in {}",
+                missing,
+                locale
+            );
+            let unknown: Vec<_> = ids.difference(&fallback).collect();
+            assert!(
+                unknown.is_empty(),
+                "{:?} in {} are not in {}",
+                unknown,
+                locale,
+                FALLBACK_LOCALE
+            );
+        }
+    }
 }
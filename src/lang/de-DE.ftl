@@ -0,0 +1,159 @@
+### message above game board
+game-won = Gewonnen!
+game-time = Deine Zeit: {$time}
+new-record = Neuer Rekord!
+time-stats = Bestzeit: {$best}, Durchschnitt: {$average} ({$count} Spiele)
+
+### difficulty level
+difficulty-Easy = Leicht
+difficulty-Medium = Mittel
+difficulty-Hard = Schwierig
+
+difficulty-display = Schwierigkeit: {$difficulty}
+daily-display = Tagesrätsel: {$date}
+rating-display = Bewertung: {$rating}, {$steps} Schritte, schwierigste Technik: {$technique}
+rating-unsolvable = Bewertung: nur durch Raten lösbar
+time-display = Zeit: {$time}
+
+### solving techniques
+technique-Pair = Paar-Regel
+technique-NoTriple = Dreier-Regel
+technique-Balance = Gleichgewichts-Regel
+technique-Uniqueness = Eindeutigkeits-Regel
+technique-LineAnalysis = Linien-Analyse
+technique-none = keine
+
+### buttons
+new-game = Neues Spiel
+daily-puzzle = Tagesrätsel
+custom-game = Eigenes Spiel…
+custom-size = Spielfeldgröße
+custom-difficulty = Schwierigkeit
+custom-start = Eigenes Spiel starten
+custom-display = Eigenes Spiel: {$size}×{$size}, {$difficulty}
+design-puzzle = Rätsel entwerfen
+editor-display = Rätsel-Editor: {$size}×{$size}
+editor-no-solution = Dieses Rätsel hat keine Lösung.
+editor-unique = Dieses Rätsel hat genau eine Lösung.
+editor-multiple = Dieses Rätsel hat mehr als eine Lösung. Füge Vorgaben hinzu, damit es eindeutig wird.
+editor-play = Rätsel spielen
+editor-export = Rätsel exportieren
+puzzle-ambiguous = Dieses Rätsel hat mehr als eine Lösung. Jedes volle Spielfeld, das die Regeln erfüllt, gilt als gelöst, aber Felder können nicht geprüft oder aufgedeckt werden.
+language-picker = Sprache
+clear-board = Grüne Felder löschen
+undo = Rückgängig
+redo = Wiederholen
+notes-on = Notizen: an
+notes-off = Notizen: aus
+notes-tooltip = Wenn an, notiert ein Klick X oder O als Kandidaten, statt das Feld zu setzen
+hint = Tipp
+check = Prüfen
+check-tooltip = Vergleicht deine Felder mit der Lösung, falsche Felder zählen als Fehler
+check-correct = Alle ausgefüllten Felder sind richtig.
+check-wrong = {$count ->
+    [one] Ein Feld ist falsch.
+   *[other] {$count} Felder sind falsch.
+}
+check-wrong-field = Dieses Feld weicht von der Lösung ab.
+check-unavailable = Dieses Rätsel hat keine eindeutige Lösung zum Vergleichen.
+mistakes = Fehler: {$count}
+reveal-cell = Feld aufdecken
+reveal-cell-tooltip = Setzt das ausgewählte Feld auf die Lösung. Ein Feld wird per Klick oder mit der Tastatur ausgewählt.
+reveal-solution = Lösung aufdecken
+reveal-cell-confirm = Das ausgewählte Feld aufdecken? Das Spiel zählt dann nicht mehr für deine Rekorde.
+reveal-solution-confirm = Die ganze Lösung aufdecken? Das Spiel zählt dann nicht mehr für deine Rekorde.
+reveal-confirm = Aufdecken
+reveal-cancel = Abbrechen
+revealed-count = {$count ->
+    [one] Ein Feld wurde aufgedeckt.
+   *[other] {$count} Felder wurden aufgedeckt.
+}
+helper-on = Hilfe: an
+helper-off = Hilfe: aus
+helper-tooltip = Hebt Fehler hervor, wenn ausgewählt
+share-progress = Link mit Fortschritt
+share-progress-tooltip = Schreibt das Rätsel und deine Eingaben in die Seitenadresse zum Teilen
+statistics = Statistik
+transfer = Import / Export
+close = Schließen
+generation-progress = Ein neues {$size}×{$size}-Rätsel wird erzeugt (Versuch {$attempt}), spiel solange weiter…
+generation-first = Ein {$size}×{$size}-Rätsel wird erzeugt (Versuch {$attempt})…
+generation-cancel = Abbrechen
+
+### hints
+hint-pair = Paar-Regel: Neben zwei gleichen Nachbarn muss ein {$field} stehen.
+hint-no-triple = Dreier-Regel: Zwischen zwei gleichen Symbolen muss ein {$field} stehen.
+hint-balance = Gleichgewichts-Regel: Die Linie hat schon zur Hälfte das andere Symbol, also ist das markierte Feld ein {$field}.
+hint-uniqueness = Eindeutigkeits-Regel: Die andere Wahl würde eine vollständige Linie kopieren, also ist das markierte Feld ein {$field}.
+hint-line-analysis = Linien-Analyse: In jeder gültigen Vervollständigung der Linie ist das markierte Feld ein {$field}.
+hint-none = Kein Tipp gefunden. Prüfe deine Eingaben auf Fehler.
+
+### statistics
+stats-header = Statistik
+stats-started = Begonnen
+stats-won = Gewonnen
+stats-win-rate = Gewinnquote
+stats-current-streak = Aktuelle Serie
+stats-best-streak = Längste Serie
+stats-assisted = Mit aufgedeckten Feldern
+stats-times = Gewonnene Spiele nach Zeit
+
+### screen reader labels
+board-label = Binoxxo-Spielfeld mit {$size} Zeilen und Spalten
+cell-label = Zeile {$row}, Spalte {$col}, {$field}, {$kind}
+field-X = X
+field-O = O
+field-Empty = leer
+cell-given = vorgegeben
+cell-guess = geraten
+cell-revealed = aufgedeckt
+cell-error = Fehler
+conflict-triple = Drei gleiche Symbole stehen nebeneinander.
+conflict-imbalance = Eine Zeile oder Spalte durch dieses Feld enthält mehr von einem Symbol als vom anderen.
+conflict-duplicate = Eine Zeile oder Spalte durch dieses Feld gleicht einer anderen vollständigen.
+announce-won = Gelöst! Gratulation.
+announce-invalid = Das Spielfeld ist voll, verletzt aber eine Regel.
+announce-errors = {$count ->
+    [one] Ein Feld verletzt eine Regel.
+   *[other] {$count} Felder verletzen eine Regel.
+}
+
+### import and export
+transfer-header = Import und Export
+export-header = Export
+export-copy = In die Zwischenablage kopieren
+export-copied = Kopiert!
+export-copy-failed = Kopieren ist in diesem Browser nicht möglich. Bitte den Text markieren und von Hand kopieren.
+import-header = Import
+import-help = Füge ein Rätsel mit einer Zeile pro Reihe ein: X und O für Vorgaben, . für leere Felder.
+import-file = Oder lade eine Textdatei
+import-puzzle = Rätsel starten
+import-error-empty = Der Text enthält kein Rätsel.
+import-error-char = Unbekanntes Zeichen "{$char}" in Zeile {$row}, Spalte {$col}.
+import-error-row-length = Zeile {$row} hat {$length} Felder, aber das Rätsel hat {$size} Zeilen.
+import-error-size = Ein Rätsel mit {$size} Zeilen wird nicht unterstützt. Die Größe muss gerade sein und zwischen 4 und 16 liegen.
+import-error-rules = Das Rätsel verletzt die Regeln.
+import-error-no-solution = Das Rätsel hat keine Lösung.
+import-error-ambiguous = Das Rätsel hat mehr als eine Lösung. Nur Rätsel mit genau einer Lösung können importiert werden.
+
+### general text
+language-name = Deutsch
+header = Spiel Binoxxo
+rules-header = Regeln
+rule-1 = Niemals mehr als zwei benachbarte Xs oder Os
+rule-2 = Jede Zeile und jede Spalte müssen die gleiche Anzahl von Xs and Os enthalten
+rule-3 = Jede Zeile und jede Spalte kommt nur einmal vor
+keyboard-help = Tastatur: Pfeiltasten oder WASD bewegen den Cursor, X und O setzen ein Feld, Leertaste oder Rücktaste löschen es, U macht den letzten Zug rückgängig, N schaltet auf Notizen um.
+
+### footer
+app-name = Binoxxo
+version = Version
+version-unknown = Unbekannt
+diagnostics = {$count ->
+    [one] ein Übersetzungsproblem
+   *[other] {$count} Übersetzungsprobleme
+}
+diagnostics-header = Übersetzungsprobleme
+diagnostics-locale = Sprache
+diagnostics-id = Meldung
+diagnostics-problem = Problem
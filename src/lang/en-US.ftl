@@ -0,0 +1,159 @@
+### message above game board
+game-won = Success!
+game-time = Your time: {$time}
+new-record = New record!
+time-stats = Best time: {$best}, average: {$average} ({$count} games)
+
+### difficulty level
+difficulty-Easy = Easy
+difficulty-Medium = Medium
+difficulty-Hard = Hard
+
+difficulty-display = Difficulty: {$difficulty}
+daily-display = Daily puzzle: {$date}
+rating-display = Rating: {$rating}, {$steps} steps, hardest technique: {$technique}
+rating-unsolvable = Rating: cannot be solved without guessing
+time-display = Time: {$time}
+
+### solving techniques
+technique-Pair = Pair rule
+technique-NoTriple = No-triple rule
+technique-Balance = Balance rule
+technique-Uniqueness = Uniqueness rule
+technique-LineAnalysis = Line analysis
+technique-none = none
+
+### buttons
+new-game = New game
+daily-puzzle = Daily puzzle
+custom-game = Custom game…
+custom-size = Board size
+custom-difficulty = Difficulty
+custom-start = Start custom game
+custom-display = Custom game: {$size}×{$size}, {$difficulty}
+design-puzzle = Design puzzle
+editor-display = Puzzle editor: {$size}×{$size}
+editor-no-solution = This puzzle has no solution.
+editor-unique = This puzzle has exactly one solution.
+editor-multiple = This puzzle has more than one solution. Add givens to make it unique.
+editor-play = Play puzzle
+editor-export = Export puzzle
+puzzle-ambiguous = This puzzle has more than one solution. Every full board that follows the rules counts as solved, but fields cannot be checked or revealed.
+language-picker = Language
+clear-board = Clear Board
+undo = Undo
+redo = Redo
+notes-on = Notes: on
+notes-off = Notes: off
+notes-tooltip = When on, clicking a field notes X or O as candidates instead of setting it
+hint = Hint
+check = Check
+check-tooltip = Compares your fields with the solution, wrong fields count as mistakes
+check-correct = All filled fields are correct.
+check-wrong = {$count ->
+    [one] One field is wrong.
+   *[other] {$count} fields are wrong.
+}
+check-wrong-field = This field differs from the solution.
+check-unavailable = This puzzle has no unique solution to compare with.
+mistakes = Mistakes: {$count}
+reveal-cell = Reveal field
+reveal-cell-tooltip = Sets the selected field to the solution. Select a field by clicking it or with the keyboard.
+reveal-solution = Reveal solution
+reveal-cell-confirm = Reveal the selected field? The game will no longer count for your records.
+reveal-solution-confirm = Reveal the whole solution? The game will no longer count for your records.
+reveal-confirm = Reveal
+reveal-cancel = Cancel
+revealed-count = {$count ->
+    [one] One field was revealed.
+   *[other] {$count} fields were revealed.
+}
+helper-on = Help: on
+helper-off = Help: off
+helper-tooltip = Will highlight errors, when selected
+share-progress = Link with progress
+share-progress-tooltip = Puts the puzzle and your guesses into the page address for sharing
+statistics = Statistics
+transfer = Import / Export
+close = Close
+generation-progress = Generating a new {$size}×{$size} puzzle (attempt {$attempt}), keep playing meanwhile…
+generation-first = Generating a {$size}×{$size} puzzle (attempt {$attempt})…
+generation-cancel = Cancel
+
+### hints
+hint-pair = Pair rule: next to two equal neighbors there must be an {$field}.
+hint-no-triple = No-triple rule: between two equal symbols there must be an {$field}.
+hint-balance = Balance rule: the line already has half of its fields of the other symbol, so the highlighted field is an {$field}.
+hint-uniqueness = Uniqueness rule: the other choice would copy a complete line, so the highlighted field is an {$field}.
+hint-line-analysis = Line analysis: in every valid completion of the line the highlighted field is an {$field}.
+hint-none = No hint found. Check your guesses for errors.
+
+### statistics
+stats-header = Statistics
+stats-started = Started
+stats-won = Won
+stats-win-rate = Win rate
+stats-current-streak = Current streak
+stats-best-streak = Best streak
+stats-assisted = With revealed fields
+stats-times = Won games by time
+
+### screen reader labels
+board-label = Binoxxo board with {$size} rows and columns
+cell-label = row {$row}, column {$col}, {$field}, {$kind}
+field-X = X
+field-O = O
+field-Empty = empty
+cell-given = given
+cell-guess = guess
+cell-revealed = revealed
+cell-error = error
+conflict-triple = Three equal symbols are next to each other.
+conflict-imbalance = A row or column through this field has more of one symbol than of the other.
+conflict-duplicate = A row or column through this field equals another complete one.
+announce-won = Solved! Congratulations.
+announce-invalid = The board is full, but breaks a rule.
+announce-errors = {$count ->
+    [one] One field breaks a rule.
+   *[other] {$count} fields break a rule.
+}
+
+### import and export
+transfer-header = Import and export
+export-header = Export
+export-copy = Copy to clipboard
+export-copied = Copied!
+export-copy-failed = Copying is not possible in this browser. Please select the text and copy it by hand.
+import-header = Import
+import-help = Paste a puzzle with one line per row: X and O for givens, . for empty fields.
+import-file = Or load a text file
+import-puzzle = Start puzzle
+import-error-empty = The text contains no puzzle.
+import-error-char = Unknown character "{$char}" in row {$row}, column {$col}.
+import-error-row-length = Row {$row} has {$length} fields, but the puzzle has {$size} rows.
+import-error-size = A puzzle with {$size} rows is not supported. The size must be even and between 4 and 16.
+import-error-rules = The puzzle breaks the rules.
+import-error-no-solution = The puzzle has no solution.
+import-error-ambiguous = The puzzle has more than one solution. Only puzzles with exactly one solution can be imported.
+
+### general text
+language-name = English
+header = Let's play Binoxxo
+rules-header = Rules
+rule-1 = Never more than two adjacent Xs or Os
+rule-2 = Every row and column must contain the same number of Xs and Os
+rule-3 = Every row and every column is unique
+keyboard-help = Keyboard: arrow keys or WASD move the cursor, X and O set a field, space or backspace clears it, U undoes the last move, N switches to notes.
+
+### footer
+app-name = Binoxxo
+version = version
+version-unknown = unknown
+diagnostics = {$count ->
+    [one] one translation problem
+   *[other] {$count} translation problems
+}
+diagnostics-header = Translation problems
+diagnostics-locale = Language
+diagnostics-id = Message
+diagnostics-problem = Problem
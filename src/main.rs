@@ -1,19 +1,98 @@
+mod check;
+mod conflicts;
 mod control;
+mod generator;
+mod hint;
+mod history;
+mod keyboard;
 mod lang;
 mod model;
+mod rating;
+mod records;
+mod share;
+mod solutions;
+mod solver;
+mod stats;
+mod text_format;
+mod timer;
 mod view;
 
-use crate::control::{update, Message, DIFFICULTY_STORAGE, HELPER_STORAGE, LANGUAGE_STORAGE};
-use crate::model::Model;
+use crate::control::{
+    browser_languages, check_design, load_shared_puzzle, now, show_puzzle_url, start_generation,
+    update, Message, CUSTOM_GAME_STORAGE, DIFFICULTY_STORAGE, GAME_LOG_STORAGE, GAME_STORAGE,
+    HELPER_STORAGE, LANGUAGE_STORAGE, RECORDS_STORAGE,
+};
+use crate::keyboard::key_action;
+use crate::model::{CustomGame, GameResult, Generation, Language, Model, SavedGame};
 use crate::view::view;
+use binoxxo::rules::is_board_valid;
 use seed::browser::web_storage::LocalStorage;
 use seed::prelude::*;
 
-fn init(_url: Url, _orders: &mut impl Orders<Message>) -> Model {
+fn init(url: Url, orders: &mut impl Orders<Message>) -> Model {
     let difficulty = LocalStorage::get(DIFFICULTY_STORAGE).unwrap_or_default();
-    let language = LocalStorage::get(LANGUAGE_STORAGE).unwrap_or_default();
+    let language = LocalStorage::get(LANGUAGE_STORAGE)
+        .unwrap_or_else(|_| Language::negotiate(&browser_languages()));
     let helper = LocalStorage::get(HELPER_STORAGE).unwrap_or_default();
-    Model::new(difficulty, helper, language)
+    let game_log = LocalStorage::get(GAME_LOG_STORAGE).unwrap_or_default();
+    // fall back to a new game, if the stored game is missing or corrupt
+    let saved = LocalStorage::get(GAME_STORAGE)
+        .ok()
+        .and_then(|saved: SavedGame| Model::from_saved(&saved, helper, language));
+    let restored = saved.is_some();
+    let mut model = saved.unwrap_or_else(|| Model::waiting(difficulty, helper, language));
+    model.game_log = game_log;
+    model.records = LocalStorage::get(RECORDS_STORAGE).unwrap_or_default();
+    model.custom = LocalStorage::get(CUSTOM_GAME_STORAGE)
+        .ok()
+        .filter(CustomGame::is_valid)
+        .unwrap_or_default();
+    model.now = now();
+    if model.editing {
+        check_design(&mut model);
+    } else if !model.waiting {
+        model.timer.start(model.now);
+    }
+    // a shared puzzle logs its own start, so does the generated puzzle once it is ready
+    if !load_shared_puzzle(&mut model, &url) && !restored {
+        start_generation(&mut model, Generation::new(difficulty), orders);
+    }
+    if !model.waiting {
+        show_puzzle_url(&model, false);
+    }
+    if !model.editing && is_board_valid(&model.board) {
+        // restored an already won game, do not count it again
+        model.timer.pause(model.now);
+        model.result = Some(GameResult {
+            time_ms: model.timer.elapsed_ms(model.now) as u64,
+            new_record: false,
+        });
+    }
+
+    orders.subscribe(|subs::UrlChanged(url)| Message::LoadUrl(url));
+    orders.stream(streams::interval(1000, || Message::Tick));
+    orders.stream(streams::document_event(Ev::VisibilityChange, |_| {
+        Message::VisibilityChanged
+    }));
+    orders.stream(streams::window_event(Ev::KeyDown, |event| {
+        let event: web_sys::KeyboardEvent = event.unchecked_into();
+        // typing into form fields must not play, neither must pressing buttons and links
+        let tag = event
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+            .map(|el| el.tag_name());
+        let in_form = matches!(tag.as_deref(), Some("INPUT" | "TEXTAREA" | "SELECT"));
+        let presses_control = matches!(tag.as_deref(), Some("BUTTON" | "A"))
+            && matches!(event.key().as_str(), " " | "Enter");
+        if in_form || presses_control || event.ctrl_key() || event.alt_key() || event.meta_key() {
+            return None;
+        }
+        let action = key_action(&event.key())?;
+        // keep arrows and space from scrolling the page
+        event.prevent_default();
+        Some(Message::Key(action))
+    }));
+    model
 }
 
 fn main() {
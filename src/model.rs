@@ -1,10 +1,34 @@
-use crate::lang::ResourceManager;
+use crate::check::CheckState;
+use crate::control::{CellPos, Reveal};
+use crate::generator::create_seeded_puzzle_board;
+use crate::hint::HintState;
+use crate::history::History;
+use crate::lang::{ResourceManager, FALLBACK_LOCALE, LANGUAGES};
+use crate::rating::{PuzzleSearch, Rating};
+use crate::records::Records;
+use crate::solutions::{count_solutions, random_board, SolutionCount};
+use crate::stats::GameLog;
+use crate::text_format::TransferState;
+use crate::timer::Timer;
 use binoxxo::bruteforce::create_puzzle_board;
 use binoxxo::field::{Board, Field};
+use binoxxo::rules::is_move_valid;
+use rand::{thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use seed::prelude::CmdHandle;
+use serde::de::Error;
+use serde::{Deserializer, Serializer};
 use serde_derive::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 
-#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+/// Smallest side length of a board.
+pub const MIN_SIZE: usize = 4;
+/// Largest side length of a board.
+pub const MAX_SIZE: usize = 16;
+
+/// Ordered from easiest to hardest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Difficulty {
     Easy,
     Medium,
@@ -21,12 +45,218 @@ impl fmt::Display for Difficulty {
     }
 }
 
+impl Difficulty {
+    /// Returns the difficulty, whose puzzles have side length `size`.
+    pub fn from_size(size: usize) -> Option<Difficulty> {
+        match size {
+            6 => Some(Difficulty::Easy),
+            8 => Some(Difficulty::Medium),
+            10 => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    /// Returns side length and `guesses` for the puzzle generator.
+    fn board_params(self) -> (usize, usize) {
+        match self {
+            Difficulty::Easy => (6, 5),
+            Difficulty::Medium => (8, 10),
+            Difficulty::Hard => (10, 15),
+        }
+    }
+}
+
 impl Default for Difficulty {
     fn default() -> Self {
         Difficulty::Easy
     }
 }
 
+/// Largest board size, which `create_puzzle_board` generates reliably.
+/// Larger boards start from a random complete board.
+const MAX_BRUTEFORCE_SIZE: usize = 10;
+
+/// Returns the number of random `guesses` for the puzzle generator on boards of side length `size`.
+fn guesses_for_size(size: usize) -> usize {
+    size * size / 7
+}
+
+/// Board size and target difficulty chosen for a custom game.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CustomGame {
+    pub size: usize,
+    pub difficulty: Difficulty,
+}
+
+impl Default for CustomGame {
+    fn default() -> Self {
+        CustomGame {
+            size: 8,
+            difficulty: Difficulty::Medium,
+        }
+    }
+}
+
+impl CustomGame {
+    /// Returns all side lengths, which can be chosen.
+    pub fn sizes() -> impl Iterator<Item = usize> {
+        (MIN_SIZE..=MAX_SIZE).step_by(2)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        CustomGame::sizes().any(|size| size == self.size)
+    }
+}
+
+/// A new puzzle generated in small steps, so the page stays responsive.
+#[derive(Debug)]
+pub struct Generation {
+    pub difficulty: Difficulty,
+    size: usize,
+    guesses: usize,
+    /// date of the daily puzzle, `None` for random puzzles
+    pub daily: Option<String>,
+    /// whether the puzzle is for a custom game
+    pub custom: bool,
+    rng: ChaCha8Rng,
+    search: PuzzleSearch,
+    /// next scheduled step, which is aborted when the generation is dropped
+    pub next_step: Option<CmdHandle>,
+}
+
+impl Generation {
+    pub fn new(difficulty: Difficulty) -> Generation {
+        let (size, guesses) = difficulty.board_params();
+        Generation::random(difficulty, size, guesses)
+    }
+
+    /// Generates a puzzle with the board size and difficulty of `custom`.
+    pub fn custom(custom: CustomGame) -> Generation {
+        let size = custom.size;
+        Generation {
+            custom: true,
+            ..Generation::random(custom.difficulty, size, guesses_for_size(size))
+        }
+    }
+
+    fn random(difficulty: Difficulty, size: usize, guesses: usize) -> Generation {
+        Generation {
+            difficulty,
+            size,
+            guesses,
+            daily: None,
+            custom: false,
+            rng: ChaCha8Rng::seed_from_u64(thread_rng().gen()),
+            search: PuzzleSearch::new(difficulty),
+            next_step: None,
+        }
+    }
+
+    /// Generates the daily puzzle of `date`, which is the same for every player.
+    pub fn daily(difficulty: Difficulty, seed: u64, date: String) -> Generation {
+        let (size, guesses) = difficulty.board_params();
+        Generation {
+            difficulty,
+            size,
+            guesses,
+            daily: Some(date),
+            custom: false,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            search: PuzzleSearch::new(difficulty),
+            next_step: None,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of puzzles tried so far.
+    pub fn attempts(&self) -> usize {
+        self.search.attempts()
+    }
+
+    /// Runs one piece of the search: creating a puzzle, adding or removing one of its givens,
+    /// or counting its solutions. Returns the puzzle once the search is finished.
+    pub fn step(&mut self) -> Option<Board> {
+        if !self.search.needs_puzzle() {
+            return self.search.step(&mut self.rng);
+        }
+        let puzzle = if self.daily.is_some() {
+            create_seeded_puzzle_board(self.rng.gen(), self.size, self.guesses)
+        } else if self.size > MAX_BRUTEFORCE_SIZE {
+            random_board(self.size, &mut self.rng)
+        } else {
+            create_puzzle_board(self.size, self.guesses)
+        };
+        self.search.start(puzzle);
+        None
+    }
+}
+
+/// Sets field at column `col` and row `row` to `field` including `Empty`.
+pub fn set_field(board: &mut Board, col: usize, row: usize, field: Field) {
+    if field == Field::Empty {
+        if Field::Empty != board.get(col, row) {
+            board.clear(col, row);
+        }
+    } else {
+        board.set(col, row, field);
+    }
+}
+
+/// Returns whether any field of `board` breaks a rule.
+pub fn breaks_rules(board: &Board) -> bool {
+    let size = board.get_size();
+    (0..size)
+        .any(|x| (0..size).any(|y| Field::Empty != board.get(x, y) && !is_move_valid(board, x, y)))
+}
+
+/// Number of placed `X` and `O` in a row or column.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LineCount {
+    pub x: usize,
+    pub o: usize,
+}
+
+impl LineCount {
+    fn of(fields: impl Iterator<Item = Field>) -> LineCount {
+        fields.fold(LineCount::default(), |count, field| match field {
+            Field::X => LineCount {
+                x: count.x + 1,
+                ..count
+            },
+            Field::O => LineCount {
+                o: count.o + 1,
+                ..count
+            },
+            Field::Empty => count,
+        })
+    }
+
+    /// Returns the counts of all rows of `board`.
+    pub fn rows(board: &Board) -> Vec<LineCount> {
+        let size = board.get_size();
+        (0..size)
+            .map(|row| LineCount::of((0..size).map(|col| board.get(col, row))))
+            .collect()
+    }
+
+    /// Returns the counts of all columns of `board`.
+    pub fn columns(board: &Board) -> Vec<LineCount> {
+        let size = board.get_size();
+        (0..size)
+            .map(|col| LineCount::of((0..size).map(|row| board.get(col, row))))
+            .collect()
+    }
+
+    /// Returns whether one symbol fills half of a line of length `size`,
+    /// so the remaining fields must get the other symbol.
+    pub fn is_complete(&self, size: usize) -> bool {
+        self.x >= size / 2 || self.o >= size / 2
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Editable {
     editable: Vec<bool>,
@@ -50,39 +280,152 @@ impl Editable {
     pub fn is_editable(&self, x: usize, y: usize) -> bool {
         self.editable[x + self.size * y]
     }
+
+    /// Returns a board with only the givens of `board`.
+    pub fn givens(&self, board: &Board) -> Board {
+        let mut givens = board.clone();
+        for x in 0..self.size {
+            for y in 0..self.size {
+                if self.is_editable(x, y) {
+                    set_field(&mut givens, x, y, Field::Empty);
+                }
+            }
+        }
+        givens
+    }
+}
+
+/// Candidate marks the player noted on an empty field.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Marks {
+    pub x: bool,
+    pub o: bool,
+}
+
+impl Marks {
+    pub fn is_empty(&self) -> bool {
+        !self.x && !self.o
+    }
+
+    /// Adds or removes the mark of `field`.
+    pub fn toggle(self, field: Field) -> Marks {
+        match field {
+            Field::X => Marks { x: !self.x, ..self },
+            Field::O => Marks { o: !self.o, ..self },
+            Field::Empty => self,
+        }
+    }
+
+    /// Cycles through no marks, `X`, `O` and both marks.
+    pub fn next(self) -> Marks {
+        match (self.x, self.o) {
+            (false, false) => Marks { x: true, o: false },
+            (true, false) => Marks { x: false, o: true },
+            (false, true) => Marks { x: true, o: true },
+            (true, true) => Marks::default(),
+        }
+    }
+}
+
+/// Pencil marks of all fields, kept beside the board, as fields hold only `X` or `O`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Notes {
+    marks: Vec<Marks>,
+    size: usize,
+}
+
+impl Notes {
+    pub fn new(size: usize) -> Notes {
+        Notes {
+            marks: vec![Marks::default(); size * size],
+            size,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.marks.iter().all(Marks::is_empty)
+    }
+
+    fn fits(&self, size: usize) -> bool {
+        self.size == size && self.marks.len() == size * size
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Marks {
+        self.marks[x + self.size * y]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, marks: Marks) {
+        self.marks[x + self.size * y] = marks;
+    }
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
-pub enum Language {
-    DeDe,
-    EnUs,
+/// Language of the user interface, one for every resource file in `src/lang`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Language {
+    locale: &'static str,
 }
 
 impl Default for Language {
     fn default() -> Self {
-        Language::EnUs
+        Language::from_locale(FALLBACK_LOCALE).expect("fallback locale is missing")
     }
 }
 
-impl ToString for Language {
-    fn to_string(&self) -> String {
-        use Language::*;
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.locale)
+    }
+}
 
-        match self {
-            DeDe => "de-DE".to_string(),
-            EnUs => "en-US".to_string(),
-        }
+impl serde::Serialize for Language {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.locale)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Language {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let locale: String = serde::Deserialize::deserialize(deserializer)?;
+        // names of the former enum variants
+        let locale = match locale.as_str() {
+            "DeDe" => "de-DE",
+            "EnUs" => "en-US",
+            locale => locale,
+        };
+        Language::from_locale(locale)
+            .ok_or_else(|| D::Error::custom(format!("unknown language {}", locale)))
     }
 }
 
 impl Language {
-    pub fn next(self) -> Self {
-        use Language::*;
+    pub fn all() -> impl Iterator<Item = Language> {
+        LANGUAGES.iter().map(|(locale, _)| Language { locale })
+    }
 
-        match self {
-            DeDe => EnUs,
-            EnUs => DeDe,
+    pub fn from_locale(locale: &str) -> Option<Language> {
+        Language::all().find(|language| language.locale == locale)
+    }
+
+    pub fn locale(&self) -> &'static str {
+        self.locale
+    }
+
+    /// Returns the first supported language of the preferred language `tags`,
+    /// e.g. of `navigator.languages`. A tag like `de-AT` selects the language `de-DE`.
+    pub fn negotiate<S: AsRef<str>>(tags: &[S]) -> Language {
+        for tag in tags {
+            let tag = tag.as_ref().to_lowercase();
+            let primary = tag.split('-').next().unwrap_or_default();
+            let locale = |language: &Language| language.locale.to_lowercase();
+            let by_tag = Language::all().find(|language| locale(language) == tag);
+            let by_primary = || {
+                Language::all().find(|language| locale(language).split('-').next() == Some(primary))
+            };
+            if let Some(language) = by_tag.or_else(by_primary) {
+                return language;
+            }
         }
+        Language::default()
     }
 }
 
@@ -120,26 +463,125 @@ impl Helper {
     }
 }
 
+/// Outcome of a won game.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GameResult {
+    pub time_ms: u64,
+    pub new_record: bool,
+}
+
 #[derive(Debug)]
 pub struct Model {
     pub difficulty: Difficulty,
     pub helper: Helper,
     pub board: Board,
     pub editable: Editable,
+    pub notes: Notes,
+    /// whether clicks and keys change the pencil marks instead of the fields
+    pub note_mode: bool,
+    pub history: History,
+    pub timer: Timer,
+    /// time of the last update, used to render the timer
+    pub now: f64,
+    /// `None` until the game is won
+    pub result: Option<GameResult>,
+    pub records: Records,
+    pub game_log: GameLog,
+    pub show_statistics: bool,
+    /// date of the daily puzzle, `None` for random puzzles
+    pub daily: Option<String>,
+    /// custom games do not count for the records
+    pub is_custom: bool,
+    pub hint: HintState,
+    pub check: CheckState,
+    /// wrong fields found by checking
+    pub mistakes: usize,
+    /// reveal waiting for confirmation
+    pub pending_reveal: Option<Reveal>,
+    /// fields set to the solution on request of the player
+    pub revealed: Vec<CellPos>,
+    /// cell selected for keyboard play, `None` until the keyboard is used
+    pub cursor: Option<CellPos>,
+    /// cell selected last by the mouse or the cursor, e.g. to reveal it
+    pub selected: Option<CellPos>,
+    /// settings of the custom game form
+    pub custom: CustomGame,
+    pub show_custom: bool,
+    pub transfer: TransferState,
+    /// whether the player designs a puzzle instead of playing it
+    pub editing: bool,
+    /// solutions of the puzzle, updated live in the editor
+    pub solution_count: SolutionCount,
+    /// measured rating of the puzzle, `None` if it cannot be solved without guessing
+    pub rating: Option<Rating>,
     pub res_mgr: ResourceManager,
     pub language: Language,
+    /// new puzzle being generated while the current game goes on
+    pub generation: Option<Generation>,
+    /// whether the board only waits for the first generated puzzle and cannot be played
+    pub waiting: bool,
+    /// whether the translation problems are listed below the game
+    pub show_diagnostics: bool,
 }
 
 impl Model {
     pub fn new(difficulty: Difficulty, helper: Helper, language: Language) -> Model {
-        let (size, guesses) = match difficulty {
-            Difficulty::Easy => (6, 5),
-            Difficulty::Medium => (8, 10),
-            Difficulty::Hard => (10, 15),
+        Model::generate(Generation::new(difficulty), helper, language)
+    }
+
+    /// Creates an empty board, which is shown until the first puzzle of `difficulty` is generated.
+    /// None of its fields can be played.
+    pub fn waiting(difficulty: Difficulty, helper: Helper, language: Language) -> Model {
+        let (size, _) = difficulty.board_params();
+        let mut model = Model::from_puzzle(difficulty, helper, language, Board::new(size));
+        model.editable = Editable {
+            editable: vec![false; size * size],
+            size,
         };
+        model.waiting = true;
+        model
+    }
+
+    /// Creates an empty board of side length `size` to enter the givens of a new puzzle.
+    pub fn new_editor(size: usize, helper: Helper, language: Language) -> Model {
+        let difficulty = Difficulty::from_size(size).unwrap_or_default();
+        let mut model = Model::from_puzzle(difficulty, helper, language, Board::new(size));
+        model.editing = true;
+        model
+    }
 
-        let board = create_puzzle_board(size, guesses);
+    /// Runs all steps of `generation` at once.
+    fn generate(mut generation: Generation, helper: Helper, language: Language) -> Model {
+        loop {
+            if let Some(board) = generation.step() {
+                return Model::generated(&generation, board, helper, language);
+            }
+        }
+    }
+
+    /// Creates a game with the puzzle `board` found by `generation`.
+    pub fn generated(
+        generation: &Generation,
+        board: Board,
+        helper: Helper,
+        language: Language,
+    ) -> Model {
+        let mut model = Model::from_puzzle(generation.difficulty, helper, language, board);
+        model.daily = generation.daily.clone();
+        model.is_custom = generation.custom;
+        model
+    }
+
+    fn from_puzzle(
+        difficulty: Difficulty,
+        helper: Helper,
+        language: Language,
+        board: Board,
+    ) -> Model {
         let editable = Editable::new(&board);
+        let notes = Notes::new(board.get_size());
+        let rating = Rating::of(&board);
+        let solution_count = count_solutions(&board);
         let res_mgr = ResourceManager::new();
 
         Model {
@@ -147,8 +589,112 @@ impl Model {
             helper,
             board,
             editable,
+            notes,
+            note_mode: false,
+            history: History::default(),
+            timer: Timer::default(),
+            now: 0.0,
+            result: None,
+            records: Records::default(),
+            game_log: GameLog::default(),
+            show_statistics: false,
+            daily: None,
+            is_custom: false,
+            hint: HintState::default(),
+            check: CheckState::default(),
+            mistakes: 0,
+            pending_reveal: None,
+            revealed: Vec::new(),
+            cursor: None,
+            selected: None,
+            custom: CustomGame::default(),
+            show_custom: false,
+            transfer: TransferState::default(),
+            editing: false,
+            solution_count,
+            rating,
             res_mgr,
             language,
+            generation: None,
+            waiting: false,
+            show_diagnostics: false,
+        }
+    }
+
+    /// Creates a game from a puzzle with `givens`, which is already partially played on `board`.
+    /// Returns `None` if `board` does not contain all `givens`.
+    pub fn with_board(
+        difficulty: Difficulty,
+        helper: Helper,
+        language: Language,
+        givens: &Board,
+        board: Board,
+    ) -> Option<Model> {
+        let size = board.get_size();
+        if givens.get_size() != size {
+            return None;
+        }
+        for x in 0..size {
+            for y in 0..size {
+                let given = givens.get(x, y);
+                if Field::Empty != given && given != board.get(x, y) {
+                    return None;
+                }
+            }
+        }
+
+        let mut model = Model::from_puzzle(difficulty, helper, language, givens.clone());
+        model.board = board;
+        Some(model)
+    }
+
+    /// Restores a game from `saved`.
+    /// Returns `None` if `saved` does not describe a consistent game.
+    pub fn from_saved(saved: &SavedGame, helper: Helper, language: Language) -> Option<Model> {
+        let givens = Board::from_str(&saved.givens).ok()?;
+        let board = Board::from_str(&saved.board).ok()?;
+        let mut model = Model::with_board(saved.difficulty, helper, language, &givens, board)?;
+        model.timer = Timer::with_elapsed(saved.elapsed_ms as f64);
+        model.daily = saved.daily.clone();
+        // games saved before the flag are custom, if the size does not fit the difficulty
+        model.is_custom =
+            saved.custom || Difficulty::from_size(model.get_size()) != Some(saved.difficulty);
+        model.editing = saved.editing;
+        model.mistakes = saved.mistakes;
+        model.revealed = saved
+            .revealed
+            .iter()
+            .filter(|pos| pos.col < model.get_size() && pos.row < model.get_size())
+            .copied()
+            .collect();
+        if let Some(notes) = saved.notes.as_ref().filter(|n| n.fits(model.get_size())) {
+            model.notes = notes.clone();
+        }
+        Some(model)
+    }
+
+    /// Returns the state of the current game, which is stored across page reloads.
+    pub fn to_saved(&self, now: f64) -> SavedGame {
+        SavedGame {
+            difficulty: self.difficulty,
+            givens: self.editable.givens(&self.board).to_string(),
+            board: self.board.to_string(),
+            elapsed_ms: self.timer.elapsed_ms(now) as u64,
+            daily: self.daily.clone(),
+            custom: self.is_custom,
+            editing: self.editing,
+            notes: Some(self.notes.clone()).filter(|notes| !notes.is_empty()),
+            mistakes: self.mistakes,
+            revealed: self.revealed.clone(),
+        }
+    }
+
+    /// Returns the givens of the puzzle, in the editor these are all fields of the board.
+    pub fn puzzle_givens(&self) -> Board {
+        if self.editing {
+            self.board.clone()
+        } else {
+            self.editable.givens(&self.board)
         }
     }
 
@@ -157,6 +703,28 @@ impl Model {
     }
 }
 
+/// Game state as stored in `LocalStorage`.
+/// Boards are stored in the text format of `Board::to_string`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub difficulty: Difficulty,
+    pub givens: String,
+    pub board: String,
+    pub elapsed_ms: u64,
+    #[serde(default)]
+    pub daily: Option<String>,
+    #[serde(default)]
+    pub custom: bool,
+    #[serde(default)]
+    pub editing: bool,
+    #[serde(default)]
+    pub notes: Option<Notes>,
+    #[serde(default)]
+    pub mistakes: usize,
+    #[serde(default)]
+    pub revealed: Vec<CellPos>,
+}
+
 impl Default for Model {
     fn default() -> Self {
         Model::new(
@@ -166,3 +734,229 @@ impl Default for Model {
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn saved_game() -> SavedGame {
+        SavedGame {
+            difficulty: Difficulty::Medium,
+            givens: "X _ _ _\n_ _ _ _\n_ _ O _\n_ _ _ _\n".to_string(),
+            board: "X O _ _\n_ _ _ _\n_ _ O _\n_ X _ _\n".to_string(),
+            elapsed_ms: 4200,
+            daily: Some("2026-10-18".to_string()),
+            custom: true,
+            editing: false,
+            notes: None,
+            mistakes: 2,
+            revealed: vec![CellPos { col: 1, row: 0 }],
+        }
+    }
+
+    #[test]
+    fn restore_saved_game() {
+        let saved = saved_game();
+        let model = Model::from_saved(
+            &saved,
+            Helper::Enabled,
+            Language::from_locale("de-DE").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(Difficulty::Medium, model.difficulty);
+        assert_eq!(Field::O, model.board.get(1, 0));
+        assert!(!model.editable.is_editable(0, 0));
+        assert!(model.editable.is_editable(1, 0));
+        assert_eq!(saved, model.to_saved(0.0));
+    }
+
+    #[test]
+    fn reject_inconsistent_saved_game() {
+        let mut saved = saved_game();
+        saved.board = "O O _ _\n_ _ _ _\n_ _ O _\n_ X _ _\n".to_string();
+        assert!(Model::from_saved(
+            &saved,
+            Helper::Enabled,
+            Language::from_locale("de-DE").unwrap()
+        )
+        .is_none());
+
+        let mut saved = saved_game();
+        saved.givens = "garbage".to_string();
+        assert!(Model::from_saved(
+            &saved,
+            Helper::Enabled,
+            Language::from_locale("de-DE").unwrap()
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn generate_in_steps() {
+        let date = "2024-03-01".to_string();
+        let mut generation = Generation::daily(Difficulty::Easy, 42, date.clone());
+        let board = loop {
+            if let Some(board) = generation.step() {
+                break board;
+            }
+        };
+        assert!(generation.attempts() >= 1);
+        let model = Model::generated(&generation, board, Helper::Enabled, Language::default());
+        assert_eq!(Some(date.clone()), model.daily);
+        assert_eq!(SolutionCount::Unique, model.solution_count);
+
+        // the daily puzzle does not depend on how the steps are run
+        let at_once = Model::generate(
+            Generation::daily(Difficulty::Easy, 42, date),
+            Helper::Enabled,
+            Language::default(),
+        );
+        assert_eq!(model.board, at_once.board);
+    }
+
+    #[test]
+    fn waiting_board_cannot_be_played() {
+        let model = Model::waiting(Difficulty::Medium, Helper::Enabled, Language::default());
+        assert!(model.waiting);
+        assert_eq!(8, model.get_size());
+        assert!((0..8).all(|col| (0..8).all(|row| !model.editable.is_editable(col, row))));
+    }
+
+    #[test]
+    fn generate_every_offered_size() {
+        for size in CustomGame::sizes() {
+            let custom = CustomGame {
+                size,
+                difficulty: Difficulty::Medium,
+            };
+            let model = Model::generate(
+                Generation::custom(custom),
+                Helper::Enabled,
+                Language::default(),
+            );
+            assert_eq!(size, model.get_size());
+            assert_eq!(SolutionCount::Unique, model.solution_count);
+        }
+    }
+
+    #[test]
+    fn create_custom_game() {
+        let custom = CustomGame {
+            size: 4,
+            difficulty: Difficulty::Easy,
+        };
+        let model = Model::generate(
+            Generation::custom(custom),
+            Helper::Enabled,
+            Language::default(),
+        );
+        assert_eq!(4, model.get_size());
+        assert_eq!(Difficulty::Easy, model.difficulty);
+        assert!(model.is_custom);
+        assert!(!Model::new(Difficulty::Easy, Helper::Enabled, Language::default()).is_custom);
+
+        // a custom game of a standard size is still custom
+        let custom = CustomGame::default();
+        let model = Model::generate(
+            Generation::custom(custom),
+            Helper::Enabled,
+            Language::default(),
+        );
+        assert_eq!(Difficulty::from_size(custom.size), Some(custom.difficulty));
+        assert!(model.is_custom);
+        let saved = model.to_saved(0.0);
+        assert!(saved.custom);
+        let restored = Model::from_saved(&saved, Helper::Enabled, Language::default()).unwrap();
+        assert!(restored.is_custom);
+    }
+
+    #[test]
+    fn custom_sizes() {
+        let sizes: Vec<usize> = CustomGame::sizes().collect();
+        assert_eq!(vec![4, 6, 8, 10, 12, 14, 16], sizes);
+        assert!(CustomGame::default().is_valid());
+        let odd = CustomGame {
+            size: 7,
+            difficulty: Difficulty::Hard,
+        };
+        assert!(!odd.is_valid());
+    }
+
+    #[test]
+    fn count_lines() {
+        let board = Board::from_str("X O X _\nX _ _ _\nO _ _ _\n_ _ _ _").unwrap();
+        let rows = LineCount::rows(&board);
+        assert_eq!(LineCount { x: 2, o: 1 }, rows[0]);
+        assert!(rows[0].is_complete(4));
+        assert!(!rows[1].is_complete(4));
+        assert_eq!(LineCount { x: 2, o: 1 }, LineCount::columns(&board)[0]);
+        assert_eq!(LineCount::default(), LineCount::columns(&board)[3]);
+    }
+
+    #[test]
+    fn cycle_and_restore_marks() {
+        let marks = Marks::default().next();
+        assert_eq!(Marks { x: true, o: false }, marks);
+        assert_eq!(Marks { x: true, o: true }, marks.toggle(Field::O));
+        assert!(marks.next().next().next().is_empty());
+
+        let mut model =
+            Model::from_saved(&saved_game(), Helper::Enabled, Language::default()).unwrap();
+        model.notes.set(3, 0, marks);
+        // marks are no fields, so they do not fill the board
+        assert_eq!(Field::Empty, model.board.get(3, 0));
+
+        let restored =
+            Model::from_saved(&model.to_saved(0.0), Helper::Enabled, Language::default()).unwrap();
+        assert_eq!(marks, restored.notes.get(3, 0));
+    }
+
+    #[test]
+    fn negotiate_language() {
+        assert_eq!(
+            "de-DE",
+            Language::negotiate(&["de-DE", "en-US"]).to_string()
+        );
+        assert_eq!("de-DE", Language::negotiate(&["fr", "de-AT"]).to_string());
+        assert_eq!("en-US", Language::negotiate(&["en-GB", "de"]).to_string());
+        assert_eq!("en-US", Language::negotiate(&["fr"]).to_string());
+        assert_eq!("en-US", Language::negotiate::<&str>(&[]).to_string());
+    }
+
+    #[test]
+    fn store_language() {
+        let german = Language::from_locale("de-DE").unwrap();
+        assert_eq!("\"de-DE\"", serde_json::to_string(&german).unwrap());
+        assert_eq!(german, serde_json::from_str("\"de-DE\"").unwrap());
+        // stored by former versions
+        assert_eq!(german, serde_json::from_str("\"DeDe\"").unwrap());
+        assert!(serde_json::from_str::<Language>("\"xx-XX\"").is_err());
+    }
+
+    #[test]
+    fn restore_editor() {
+        let mut model = Model::new_editor(6, Helper::Enabled, Language::default());
+        set_field(&mut model.board, 2, 3, Field::X);
+        assert_eq!(model.board, model.puzzle_givens());
+
+        let saved = model.to_saved(0.0);
+        let restored = Model::from_saved(&saved, Helper::Enabled, Language::default()).unwrap();
+        assert!(restored.editing);
+        assert_eq!(model.board, restored.puzzle_givens());
+    }
+
+    #[test]
+    fn detect_ambiguous_puzzle() {
+        let givens = Board::from_str("X _ _ _\n_ _ _ _\n_ _ _ _\n_ _ _ _").unwrap();
+        let model = Model::with_board(
+            Difficulty::Easy,
+            Helper::Enabled,
+            Language::default(),
+            &givens,
+            givens.clone(),
+        )
+        .unwrap();
+        assert_eq!(SolutionCount::Multiple, model.solution_count);
+    }
+}
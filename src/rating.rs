@@ -0,0 +1,326 @@
+//! Rates puzzles by the deduction techniques the logical solver needs and the
+//! number of steps it takes, and adjusts generated puzzles to the band of a target `Difficulty`.
+
+use crate::model::{set_field, Difficulty};
+use crate::solutions::{count_solutions, SolutionCount};
+use crate::solver::{solve, Technique};
+use binoxxo::field::{Board, Field};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Number of generated puzzles tried, before settling for a puzzle outside the band.
+const MAX_ATTEMPTS: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rating {
+    /// hardest technique needed, `None` for complete boards
+    pub hardest: Option<Technique>,
+    /// number of fields placed by the solver
+    pub steps: usize,
+    /// number of fields of the board
+    fields: usize,
+}
+
+impl Rating {
+    /// Rates `puzzle` or returns `None` if it cannot be solved without guessing.
+    pub fn of(puzzle: &Board) -> Option<Rating> {
+        let solution = solve(puzzle);
+        if solution.is_solved() {
+            Some(Rating {
+                hardest: solution.hardest_technique(),
+                steps: solution.steps.len(),
+                fields: puzzle.get_size() * puzzle.get_size(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the difficulty band of the rating.
+    /// The hardest technique selects the band. A puzzle with more steps than
+    /// allowed for that band counts one band harder.
+    pub fn difficulty(&self) -> Difficulty {
+        let band = match self.hardest {
+            Some(technique) if technique > max_technique(Difficulty::Medium) => Difficulty::Hard,
+            Some(technique) if technique > max_technique(Difficulty::Easy) => Difficulty::Medium,
+            _ => Difficulty::Easy,
+        };
+        if self.steps * 100 > self.fields * max_steps_percent(band) {
+            harder(band)
+        } else {
+            band
+        }
+    }
+}
+
+fn harder(difficulty: Difficulty) -> Difficulty {
+    match difficulty {
+        Difficulty::Easy => Difficulty::Medium,
+        Difficulty::Medium | Difficulty::Hard => Difficulty::Hard,
+    }
+}
+
+/// Returns the largest share of the fields in percent,
+/// which a puzzle of `difficulty` leaves to the player.
+fn max_steps_percent(difficulty: Difficulty) -> usize {
+    match difficulty {
+        Difficulty::Easy => 66,
+        Difficulty::Medium => 75,
+        Difficulty::Hard => 100,
+    }
+}
+
+/// Returns the hardest technique allowed for puzzles of `difficulty`.
+fn max_technique(difficulty: Difficulty) -> Technique {
+    match difficulty {
+        Difficulty::Easy => Technique::NoTriple,
+        Difficulty::Medium => Technique::Uniqueness,
+        Difficulty::Hard => Technique::LineAnalysis,
+    }
+}
+
+/// Adjustment of a puzzle towards the band of a target `Difficulty`, one given per step.
+/// Adds givens from the solution, while the puzzle is too hard: first the fields
+/// needing too hard techniques, then the fields the solver places first.
+/// Then removes givens in random order, as long as the puzzle stays within the band.
+#[derive(Clone, Debug)]
+struct Adjustment {
+    target: Difficulty,
+    puzzle: Board,
+    solved: Board,
+    rating: Rating,
+    /// givens left to remove, `None` while givens are added
+    removals: Option<Vec<(usize, usize)>>,
+}
+
+impl Adjustment {
+    /// Returns `None` if `puzzle` cannot be solved without guessing.
+    fn new(puzzle: Board, target: Difficulty) -> Option<Adjustment> {
+        let rating = Rating::of(&puzzle)?;
+        let solved = solve(&puzzle).board;
+        Some(Adjustment {
+            target,
+            puzzle,
+            solved,
+            rating,
+            removals: None,
+        })
+    }
+
+    /// Adds or removes one given. Returns whether the adjustment is finished.
+    fn step<R: Rng>(&mut self, rng: &mut R) -> bool {
+        if self.removals.is_none() {
+            if self.rating.difficulty() > self.target {
+                self.add_given();
+                return false;
+            }
+            let size = self.puzzle.get_size();
+            let mut givens: Vec<(usize, usize)> = (0..size)
+                .flat_map(|col| (0..size).map(move |row| (col, row)))
+                .filter(|(col, row)| Field::Empty != self.puzzle.get(*col, *row))
+                .collect();
+            givens.shuffle(rng);
+            self.removals = Some(givens);
+        }
+        let (col, row) = match self.removals.as_mut().and_then(Vec::pop) {
+            Some(pos) => pos,
+            None => return true,
+        };
+        self.puzzle.clear(col, row);
+        match Rating::of(&self.puzzle) {
+            Some(rating) if rating.difficulty() <= self.target => (),
+            _ => set_field(&mut self.puzzle, col, row, self.solved.get(col, row)),
+        }
+        false
+    }
+
+    fn add_given(&mut self) {
+        let max = max_technique(self.target);
+        let steps = solve(&self.puzzle).steps;
+        let step = steps
+            .iter()
+            .find(|step| step.technique > max)
+            .or_else(|| steps.first())
+            .expect("only puzzles with steps are too hard");
+        self.puzzle.set(step.col, step.row, step.field);
+        self.rating =
+            Rating::of(&self.puzzle).expect("givens from the solution keep the puzzle solvable");
+    }
+}
+
+/// Work left on the puzzle of the current attempt.
+#[derive(Clone, Debug)]
+enum Attempt {
+    Adjusting(Adjustment),
+    /// the puzzle waits for its solutions to be counted
+    Counting {
+        puzzle: Board,
+        is_adjusted: bool,
+    },
+}
+
+/// Search for a puzzle in the band of a target difficulty in small steps,
+/// so the search can be spread over several updates.
+#[derive(Clone, Debug)]
+pub struct PuzzleSearch {
+    target: Difficulty,
+    attempts: usize,
+    /// best puzzle outside the band
+    last: Option<Board>,
+    /// `None` until the next puzzle is created
+    current: Option<Attempt>,
+}
+
+impl PuzzleSearch {
+    pub fn new(target: Difficulty) -> PuzzleSearch {
+        PuzzleSearch {
+            target,
+            attempts: 0,
+            last: None,
+            current: None,
+        }
+    }
+
+    /// Number of puzzles tried so far.
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// Returns whether the search waits for the next created puzzle.
+    pub fn needs_puzzle(&self) -> bool {
+        self.current.is_none()
+    }
+
+    /// Starts the next attempt with the created `puzzle`.
+    pub fn start(&mut self, puzzle: Board) {
+        self.attempts += 1;
+        self.current = Some(match Adjustment::new(puzzle.clone(), self.target) {
+            Some(adjustment) => Attempt::Adjusting(adjustment),
+            None => Attempt::Counting {
+                puzzle,
+                is_adjusted: false,
+            },
+        });
+    }
+
+    /// Adds or removes one given of the created puzzle, or counts its solutions at the end.
+    /// Returns the first puzzle within the band, or the last puzzle if none matched
+    /// after `MAX_ATTEMPTS`. Returns `None` while the search goes on.
+    /// Puzzles without exactly one solution are never returned.
+    pub fn step<R: Rng>(&mut self, rng: &mut R) -> Option<Board> {
+        match self.current.take()? {
+            Attempt::Adjusting(mut adjustment) => {
+                self.current = Some(if adjustment.step(rng) {
+                    Attempt::Counting {
+                        puzzle: adjustment.puzzle,
+                        is_adjusted: true,
+                    }
+                } else {
+                    Attempt::Adjusting(adjustment)
+                });
+                None
+            }
+            Attempt::Counting {
+                puzzle,
+                is_adjusted,
+            } => self.judge(puzzle, is_adjusted),
+        }
+    }
+
+    fn judge(&mut self, candidate: Board, is_adjusted: bool) -> Option<Board> {
+        if SolutionCount::Unique == count_solutions(&candidate) {
+            if Rating::of(&candidate).map(|rating| rating.difficulty()) == Some(self.target) {
+                return Some(candidate);
+            }
+            // prefer adjusted puzzles, which at least can be solved without guessing
+            if is_adjusted || self.last.is_none() {
+                self.last = Some(candidate);
+            }
+        }
+        if self.attempts >= MAX_ATTEMPTS {
+            self.last.take()
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::generator::create_seeded_puzzle_board;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::str::FromStr;
+
+    #[test]
+    fn rate_easy_puzzle() {
+        let puzzle = Board::from_str(
+            "
+            X _ O X
+            O X _ O
+            _ X O O
+            O O X _",
+        )
+        .unwrap();
+        let rating = Rating::of(&puzzle).unwrap();
+        assert_eq!(4, rating.steps);
+        assert_eq!(Difficulty::Easy, rating.difficulty());
+    }
+
+    #[test]
+    fn long_puzzles_are_one_band_harder() {
+        let rating = |hardest, steps| Rating {
+            hardest: Some(hardest),
+            steps,
+            fields: 36,
+        };
+        assert_eq!(Difficulty::Easy, rating(Technique::Pair, 23).difficulty());
+        assert_eq!(Difficulty::Medium, rating(Technique::Pair, 24).difficulty());
+        assert_eq!(
+            Difficulty::Medium,
+            rating(Technique::Balance, 27).difficulty()
+        );
+        assert_eq!(
+            Difficulty::Hard,
+            rating(Technique::Balance, 28).difficulty()
+        );
+        assert_eq!(
+            Difficulty::Hard,
+            rating(Technique::LineAnalysis, 36).difficulty()
+        );
+    }
+
+    #[test]
+    fn do_not_rate_puzzles_needing_guesses() {
+        assert_eq!(None, Rating::of(&Board::new(6)));
+    }
+
+    #[test]
+    fn adjusted_puzzles_match_target() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        for target in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+            let mut search = PuzzleSearch::new(target);
+            let puzzle = loop {
+                if search.needs_puzzle() {
+                    search.start(create_seeded_puzzle_board(rng.gen(), 8, 10));
+                } else if let Some(puzzle) = search.step(&mut rng) {
+                    break puzzle;
+                }
+            };
+            let rating = Rating::of(&puzzle).unwrap();
+            assert_eq!(target, rating.difficulty());
+            assert!(rating.steps * 100 <= 64 * max_steps_percent(target));
+            assert_eq!(SolutionCount::Unique, count_solutions(&puzzle));
+        }
+    }
+
+    #[test]
+    fn adjusting_keeps_the_solution() {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let puzzle = create_seeded_puzzle_board(3, 6, 5);
+        let mut adjustment = Adjustment::new(puzzle.clone(), Difficulty::Hard).unwrap();
+        while !adjustment.step(&mut rng) {}
+        assert_eq!(solve(&puzzle).board, solve(&adjustment.puzzle).board);
+    }
+}
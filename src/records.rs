@@ -0,0 +1,80 @@
+//! Best and average solving times per difficulty.
+
+use crate::model::Difficulty;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimeStats {
+    pub best_ms: Option<u64>,
+    pub total_ms: u64,
+    pub count: u32,
+}
+
+impl TimeStats {
+    pub fn average_ms(&self) -> Option<u64> {
+        if self.count > 0 {
+            Some(self.total_ms / u64::from(self.count))
+        } else {
+            None
+        }
+    }
+
+    /// Adds a won game with time `ms` and returns whether it is a new record.
+    pub fn add(&mut self, ms: u64) -> bool {
+        let is_record = match self.best_ms {
+            Some(best) => ms < best,
+            None => true,
+        };
+        if is_record {
+            self.best_ms = Some(ms);
+        }
+        self.total_ms += ms;
+        self.count += 1;
+        is_record
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Records {
+    times: HashMap<Difficulty, TimeStats>,
+}
+
+impl Records {
+    pub fn get(&self, difficulty: Difficulty) -> TimeStats {
+        self.times.get(&difficulty).copied().unwrap_or_default()
+    }
+
+    /// Adds a won game and returns whether it is a new record for `difficulty`.
+    pub fn add(&mut self, difficulty: Difficulty, ms: u64) -> bool {
+        self.times.entry(difficulty).or_default().add(ms)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn best_and_average_time() {
+        let mut records = Records::default();
+        assert_eq!(None, records.get(Difficulty::Easy).best_ms);
+
+        assert!(records.add(Difficulty::Easy, 60_000));
+        assert!(!records.add(Difficulty::Easy, 90_000));
+        assert!(records.add(Difficulty::Easy, 30_000));
+
+        let easy = records.get(Difficulty::Easy);
+        assert_eq!(Some(30_000), easy.best_ms);
+        assert_eq!(Some(60_000), easy.average_ms());
+        assert_eq!(TimeStats::default(), records.get(Difficulty::Hard));
+    }
+
+    #[test]
+    fn records_survive_serialization() {
+        let mut records = Records::default();
+        records.add(Difficulty::Medium, 1234);
+        let json = serde_json::to_string(&records).unwrap();
+        assert_eq!(records, serde_json::from_str(&json).unwrap());
+    }
+}
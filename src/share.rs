@@ -0,0 +1,193 @@
+//! Compact text encoding of puzzles for shareable URL fragments.
+//!
+//! A fragment looks like `10.<givens>` or `10.<givens>.<guesses>`.
+//! The number is the board size. The boards list their fields row by row,
+//! three fields packed into one character of `ALPHABET`.
+
+use crate::model::{breaks_rules, set_field, MAX_SIZE, MIN_SIZE};
+use binoxxo::field::{Board, Field};
+
+const ALPHABET: &[u8] = b"0123456789abcdefghijklmnopq";
+const SEPARATOR: char = '.';
+
+/// Puzzle decoded from a URL fragment.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SharedPuzzle {
+    pub givens: Board,
+    /// givens plus the guesses of the sharing player
+    pub board: Board,
+}
+
+fn field_to_trit(field: Field) -> usize {
+    match field {
+        Field::Empty => 0,
+        Field::X => 1,
+        Field::O => 2,
+    }
+}
+
+fn trit_to_field(trit: usize) -> Field {
+    match trit {
+        1 => Field::X,
+        2 => Field::O,
+        _ => Field::Empty,
+    }
+}
+
+fn encode_board(board: &Board) -> String {
+    let size = board.get_size();
+    let fields: Vec<usize> = (0..size * size)
+        .map(|i| field_to_trit(board.get(i % size, i / size)))
+        .collect();
+    fields
+        .chunks(3)
+        .map(|chunk| {
+            let value = chunk
+                .iter()
+                .chain([0, 0].iter())
+                .take(3)
+                .fold(0, |v, t| v * 3 + t);
+            ALPHABET[value] as char
+        })
+        .collect()
+}
+
+fn decode_board(size: usize, encoded: &str) -> Option<Board> {
+    let cells = size * size;
+    if encoded.len() != cells.div_ceil(3) {
+        return None;
+    }
+    let mut board = Board::new(size);
+    for (pos, c) in encoded.bytes().enumerate() {
+        let value = ALPHABET.iter().position(|a| *a == c)?;
+        let trits = [value / 9, (value / 3) % 3, value % 3];
+        for (offset, trit) in trits.iter().enumerate() {
+            let i = pos * 3 + offset;
+            let field = trit_to_field(*trit);
+            if i < cells {
+                set_field(&mut board, i % size, i / size, field);
+            } else if Field::Empty != field {
+                // padding must be empty
+                return None;
+            }
+        }
+    }
+    Some(board)
+}
+
+/// Returns the URL fragment for a puzzle with `givens`.
+/// If `board` is given, the guesses on it are included, too.
+pub fn encode_puzzle(givens: &Board, board: Option<&Board>) -> String {
+    let size = givens.get_size();
+    let mut result = format!("{}{}{}", size, SEPARATOR, encode_board(givens));
+    if let Some(board) = board {
+        let mut guesses = Board::new(size);
+        for x in 0..size {
+            for y in 0..size {
+                if Field::Empty == givens.get(x, y) {
+                    set_field(&mut guesses, x, y, board.get(x, y));
+                }
+            }
+        }
+        result.push(SEPARATOR);
+        result += &encode_board(&guesses);
+    }
+    result
+}
+
+/// Decodes a URL fragment created by `encode_puzzle`.
+/// Returns `None` for malformed fragments or givens, which break the rules.
+pub fn decode_puzzle(fragment: &str) -> Option<SharedPuzzle> {
+    let mut parts = fragment.split(SEPARATOR);
+    let size: usize = parts.next()?.parse().ok()?;
+    if !(MIN_SIZE..=MAX_SIZE).contains(&size) || size % 2 == 1 {
+        return None;
+    }
+    let givens = decode_board(size, parts.next()?)?;
+    let guesses = match parts.next() {
+        Some(encoded) => Some(decode_board(size, encoded)?),
+        None => None,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+
+    if breaks_rules(&givens) {
+        return None;
+    }
+
+    let mut board = givens.clone();
+    if let Some(guesses) = guesses {
+        for x in 0..size {
+            for y in 0..size {
+                let guess = guesses.get(x, y);
+                if Field::Empty != guess {
+                    if Field::Empty != givens.get(x, y) {
+                        return None;
+                    }
+                    board.set(x, y, guess);
+                }
+            }
+        }
+    }
+
+    Some(SharedPuzzle { givens, board })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    fn givens() -> Board {
+        Board::from_str(
+            "
+            X _ _ O
+            _ _ _ _
+            _ O _ _
+            _ _ X _",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn roundtrip_givens() {
+        let fragment = encode_puzzle(&givens(), None);
+        assert_eq!(fragment, "4.9i0i10");
+
+        let puzzle = decode_puzzle(&fragment).unwrap();
+        assert_eq!(givens(), puzzle.givens);
+        assert_eq!(givens(), puzzle.board);
+    }
+
+    #[test]
+    fn roundtrip_guesses() {
+        let mut board = givens();
+        board.set(1, 0, Field::O);
+        board.set(3, 3, Field::O);
+
+        let puzzle = decode_puzzle(&encode_puzzle(&givens(), Some(&board))).unwrap();
+        assert_eq!(givens(), puzzle.givens);
+        assert_eq!(board, puzzle.board);
+    }
+
+    #[test]
+    fn reject_malformed_fragments() {
+        assert_eq!(None, decode_puzzle(""));
+        assert_eq!(None, decode_puzzle("4"));
+        assert_eq!(None, decode_puzzle("5.9i0i10"));
+        assert_eq!(None, decode_puzzle("4.9i0i1"));
+        assert_eq!(None, decode_puzzle("4.9i0i1!"));
+        assert_eq!(None, decode_puzzle("4.9i0i10.000000.0"));
+        // padding of the last character is not empty
+        assert_eq!(None, decode_puzzle("4.9i0i11"));
+    }
+
+    #[test]
+    fn reject_invalid_givens_and_guesses() {
+        // three X in the first row
+        assert_eq!(None, decode_puzzle("4.d00000"));
+        // guess on a given field
+        assert_eq!(None, decode_puzzle("4.9i0i10.900000"));
+    }
+}
@@ -0,0 +1,299 @@
+//! Counts the solutions of a puzzle to tell unique puzzles from ambiguous ones.
+//!
+//! Lines are bit masks with a set bit for every `X`. Before each guess the search
+//! narrows every row and column down to the valid lines matching its known fields
+//! and places the fields these lines agree on. Contradictions show up as a line
+//! without any matching valid line.
+
+use binoxxo::field::{Board, Field};
+use rand::Rng;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SolutionCount {
+    NoSolution,
+    Unique,
+    #[default]
+    Multiple,
+}
+
+/// Returns all lines of length `size` with as many `X` as `O` and without triples.
+fn valid_lines(size: usize) -> Vec<u32> {
+    let full = (1u32 << size) - 1;
+    let has_triple = |mask: u32| mask & (mask >> 1) & (mask >> 2) != 0;
+    (0..=full)
+        .filter(|mask| {
+            mask.count_ones() as usize == size / 2
+                && !has_triple(*mask)
+                && !has_triple(!mask & full)
+        })
+        .collect()
+}
+
+/// Known fields of all rows followed by all columns.
+#[derive(Clone)]
+struct Grid {
+    size: usize,
+    x: Vec<u32>,
+    o: Vec<u32>,
+}
+
+impl Grid {
+    fn new(board: &Board) -> Grid {
+        let size = board.get_size();
+        let mut grid = Grid {
+            size,
+            x: vec![0; 2 * size],
+            o: vec![0; 2 * size],
+        };
+        for row in 0..size {
+            for col in 0..size {
+                match board.get(col, row) {
+                    Field::X => grid.set(row, col, true),
+                    Field::O => grid.set(row, col, false),
+                    Field::Empty => (),
+                }
+            }
+        }
+        grid
+    }
+
+    fn full(&self) -> u32 {
+        (1 << self.size) - 1
+    }
+
+    fn set(&mut self, row: usize, col: usize, is_x: bool) {
+        let masks = if is_x { &mut self.x } else { &mut self.o };
+        masks[row] |= 1 << col;
+        masks[self.size + col] |= 1 << row;
+    }
+
+    /// Sets field `pos` of line `line`.
+    fn set_in_line(&mut self, line: usize, pos: usize, is_x: bool) {
+        if line < self.size {
+            self.set(line, pos, is_x);
+        } else {
+            self.set(pos, line - self.size, is_x);
+        }
+    }
+
+    fn to_board(&self) -> Board {
+        let mut board = Board::new(self.size);
+        for row in 0..self.size {
+            for col in 0..self.size {
+                if self.x[row] >> col & 1 == 1 {
+                    board.set(col, row, Field::X);
+                } else if self.o[row] >> col & 1 == 1 {
+                    board.set(col, row, Field::O);
+                }
+            }
+        }
+        board
+    }
+
+    fn unknown(&self, line: usize) -> u32 {
+        !(self.x[line] | self.o[line]) & self.full()
+    }
+
+    /// Places all fields forced by the valid lines.
+    /// Returns `false` if the known fields contradict the rules.
+    fn propagate(&mut self, lines: &[u32]) -> bool {
+        let size = self.size;
+        let full = self.full();
+        loop {
+            let mut changed = false;
+            for line in 0..2 * size {
+                let (x, o) = (self.x[line], self.o[line]);
+                // rows must differ from all complete rows, columns from all complete columns
+                let first = if line < size { 0 } else { size };
+                let complete: Vec<u32> = (first..first + size)
+                    .filter(|other| *other != line && self.unknown(*other) == 0)
+                    .map(|other| self.x[other])
+                    .collect();
+
+                let mut must_x = full;
+                let mut must_o = full;
+                let mut matches = false;
+                for candidate in lines {
+                    if candidate & x == x && candidate & o == 0 && !complete.contains(candidate) {
+                        matches = true;
+                        must_x &= candidate;
+                        must_o &= !candidate & full;
+                    }
+                }
+                if !matches {
+                    return false;
+                }
+
+                for (forced, is_x) in [(must_x & !x, true), (must_o & !o, false)] {
+                    for pos in (0..size).filter(|pos| forced >> pos & 1 == 1) {
+                        self.set_in_line(line, pos, is_x);
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                return true;
+            }
+        }
+    }
+}
+
+/// Searches solutions of `grid`, until `found` has `limit` solutions.
+/// `x_first` decides for every guess, whether `X` is tried before `O`.
+fn search(
+    mut grid: Grid,
+    lines: &[u32],
+    limit: usize,
+    found: &mut Vec<Grid>,
+    x_first: &mut impl FnMut() -> bool,
+) {
+    if !grid.propagate(lines) {
+        return;
+    }
+    // guess in the row with the fewest unknown fields
+    let row = (0..grid.size)
+        .filter(|row| grid.unknown(*row) != 0)
+        .min_by_key(|row| grid.unknown(*row).count_ones());
+    let row = match row {
+        Some(row) => row,
+        None => {
+            found.push(grid);
+            return;
+        }
+    };
+    let col = grid.unknown(row).trailing_zeros() as usize;
+    let first = x_first();
+    for is_x in [first, !first] {
+        let mut next = grid.clone();
+        next.set(row, col, is_x);
+        search(next, lines, limit, found, x_first);
+        if found.len() >= limit {
+            return;
+        }
+    }
+}
+
+/// Returns up to two solutions of `board`.
+fn find_solutions(board: &Board) -> Vec<Grid> {
+    let lines = valid_lines(board.get_size());
+    let mut found = Vec::new();
+    search(Grid::new(board), &lines, 2, &mut found, &mut || true);
+    found
+}
+
+/// Returns a random complete board of side length `size`.
+/// Unlike `binoxxo::bruteforce::create_puzzle_board` it never gives up on large boards.
+pub fn random_board<R: Rng>(size: usize, rng: &mut R) -> Board {
+    let lines = valid_lines(size);
+    let mut found = Vec::new();
+    search(
+        Grid::new(&Board::new(size)),
+        &lines,
+        1,
+        &mut found,
+        &mut || rng.gen(),
+    );
+    found
+        .pop()
+        .expect("boards of every even size have solutions")
+        .to_board()
+}
+
+/// Counts the solutions of `board`, but stops at the second one.
+pub fn count_solutions(board: &Board) -> SolutionCount {
+    match find_solutions(board).len() {
+        0 => SolutionCount::NoSolution,
+        1 => SolutionCount::Unique,
+        _ => SolutionCount::Multiple,
+    }
+}
+
+/// Returns the solution of `board`, `None` if there is none or more than one.
+pub fn unique_solution(board: &Board) -> Option<Board> {
+    match find_solutions(board).as_slice() {
+        [solution] => Some(solution.to_board()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use binoxxo::rules::is_board_valid;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::str::FromStr;
+
+    fn count(board: &str) -> SolutionCount {
+        count_solutions(&Board::from_str(board).unwrap())
+    }
+
+    #[test]
+    fn count_valid_lines() {
+        assert_eq!(6, valid_lines(4).len());
+        // 20 balanced lines, 6 of them with a triple
+        assert_eq!(14, valid_lines(6).len());
+        assert!(!valid_lines(6).contains(&0b000111));
+    }
+
+    #[test]
+    fn unique_puzzle() {
+        let found = count(
+            "
+            X _ O X
+            O X _ O
+            _ X O O
+            O O X _",
+        );
+        assert_eq!(SolutionCount::Unique, found);
+    }
+
+    #[test]
+    fn find_unique_solution() {
+        let puzzle = Board::from_str("X _ O X\nO X _ O\n_ X O O\nO O X _").unwrap();
+        let solution = Board::from_str("X O O X\nO X X O\nX X O O\nO O X X").unwrap();
+        assert_eq!(Some(solution), unique_solution(&puzzle));
+        assert_eq!(None, unique_solution(&Board::new(4)));
+    }
+
+    #[test]
+    fn ambiguous_puzzle() {
+        assert_eq!(SolutionCount::Multiple, count_solutions(&Board::new(4)));
+        assert_eq!(SolutionCount::Multiple, count_solutions(&Board::new(10)));
+    }
+
+    #[test]
+    fn random_boards_are_valid() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        for size in [4, 10, 16] {
+            let board = random_board(size, &mut rng);
+            assert!(is_board_valid(&board));
+            assert_eq!(SolutionCount::Unique, count_solutions(&board));
+        }
+    }
+
+    #[test]
+    fn unsolvable_puzzle() {
+        // both rows would have to become X X O O
+        let found = count(
+            "
+            X X _ _
+            _ _ _ _
+            X X _ _
+            _ _ _ _",
+        );
+        assert_eq!(SolutionCount::NoSolution, found);
+    }
+
+    #[test]
+    fn solved_board() {
+        let found = count(
+            "
+            X O X O
+            O X O X
+            X X O O
+            O O X X",
+        );
+        assert_eq!(SolutionCount::Unique, found);
+    }
+}
@@ -0,0 +1,351 @@
+//! Logical solver, which only uses deduction techniques a human would use.
+//!
+//! In contrast to `binoxxo::bruteforce` it never guesses. Every placed field
+//! is recorded as `Step` together with the `Technique`, which forced it.
+//! If the techniques do not suffice, the solver stops with an incomplete board.
+
+use crate::model::set_field;
+use binoxxo::field::{Board, Field};
+
+/// Deduction techniques ordered from easiest to hardest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Technique {
+    /// next to two equal neighbors there must be the other symbol
+    Pair,
+    /// between two equal symbols there must be the other symbol
+    NoTriple,
+    /// a line with half of its fields of one symbol is filled with the other symbol
+    Balance,
+    /// a line must not be completed to a copy of an already complete line
+    Uniqueness,
+    /// a field has the same value in all valid completions of its line
+    LineAnalysis,
+}
+
+/// A single field placed by the solver.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Step {
+    pub col: usize,
+    pub row: usize,
+    pub field: Field,
+    pub technique: Technique,
+}
+
+#[derive(Clone, Debug)]
+pub struct Solution {
+    /// board after applying all `steps`
+    pub board: Board,
+    pub steps: Vec<Step>,
+}
+
+impl Solution {
+    /// Returns whether the techniques sufficed to fill the entire board.
+    pub fn is_solved(&self) -> bool {
+        let size = self.board.get_size();
+        (0..size).all(|col| (0..size).all(|row| Field::Empty != self.board.get(col, row)))
+    }
+
+    pub fn hardest_technique(&self) -> Option<Technique> {
+        self.steps.iter().map(|step| step.technique).max()
+    }
+}
+
+pub fn opposite(field: Field) -> Field {
+    match field {
+        Field::X => Field::O,
+        Field::O => Field::X,
+        Field::Empty => Field::Empty,
+    }
+}
+
+/// A row or column of a board.
+struct Line {
+    positions: Vec<(usize, usize)>,
+    fields: Vec<Field>,
+}
+
+impl Line {
+    fn step(&self, pos: usize, field: Field, technique: Technique) -> Step {
+        let (col, row) = self.positions[pos];
+        Step {
+            col,
+            row,
+            field,
+            technique,
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        !self.fields.contains(&Field::Empty)
+    }
+
+    fn count(&self, field: Field) -> usize {
+        self.fields.iter().filter(|f| **f == field).count()
+    }
+}
+
+/// Returns all rows followed by all columns of `board`.
+fn lines(board: &Board) -> Vec<Line> {
+    let size = board.get_size();
+    let rows = (0..size).map(|row| (0..size).map(|col| (col, row)).collect::<Vec<_>>());
+    let cols = (0..size).map(|col| (0..size).map(|row| (col, row)).collect::<Vec<_>>());
+    rows.chain(cols)
+        .map(|positions| {
+            let fields = positions
+                .iter()
+                .map(|(col, row)| board.get(*col, *row))
+                .collect();
+            Line { positions, fields }
+        })
+        .collect()
+}
+
+fn find_pair(line: &Line, _: &[Line]) -> Option<Step> {
+    let fields = &line.fields;
+    for i in 0..fields.len() - 1 {
+        let current = fields[i];
+        if Field::Empty == current || current != fields[i + 1] {
+            continue;
+        }
+        if i > 0 && Field::Empty == fields[i - 1] {
+            return Some(line.step(i - 1, opposite(current), Technique::Pair));
+        }
+        if i + 2 < fields.len() && Field::Empty == fields[i + 2] {
+            return Some(line.step(i + 2, opposite(current), Technique::Pair));
+        }
+    }
+    None
+}
+
+fn find_no_triple(line: &Line, _: &[Line]) -> Option<Step> {
+    let fields = &line.fields;
+    for i in 0..fields.len() - 2 {
+        let current = fields[i];
+        if Field::Empty != current && Field::Empty == fields[i + 1] && current == fields[i + 2] {
+            return Some(line.step(i + 1, opposite(current), Technique::NoTriple));
+        }
+    }
+    None
+}
+
+fn find_balance(line: &Line, _: &[Line]) -> Option<Step> {
+    let half = line.fields.len() / 2;
+    let empty = line.fields.iter().position(|f| Field::Empty == *f)?;
+    for field in [Field::X, Field::O] {
+        if line.count(field) == half {
+            return Some(line.step(empty, opposite(field), Technique::Balance));
+        }
+    }
+    None
+}
+
+fn find_uniqueness(line: &Line, others: &[Line]) -> Option<Step> {
+    let half = line.fields.len() / 2;
+    let empties: Vec<usize> = (0..line.fields.len())
+        .filter(|i| Field::Empty == line.fields[*i])
+        .collect();
+    if empties.len() != 2 || line.count(Field::X) != half - 1 {
+        return None;
+    }
+    // a complete line, which agrees on all filled fields, would be copied
+    // by one of the two possible completions
+    let complete = others.iter().find(|other| {
+        other.is_complete()
+            && line
+                .fields
+                .iter()
+                .zip(other.fields.iter())
+                .all(|(f, o)| Field::Empty == *f || f == o)
+    })?;
+    let pos = empties[0];
+    Some(line.step(pos, opposite(complete.fields[pos]), Technique::Uniqueness))
+}
+
+fn has_triple(fields: &[Field]) -> bool {
+    fields
+        .windows(3)
+        .any(|w| Field::Empty != w[0] && w[0] == w[1] && w[1] == w[2])
+}
+
+/// Calls `f` for every valid completion of `fields`, which copies none of `others`.
+fn for_each_completion(
+    fields: &mut Vec<Field>,
+    empties: &[usize],
+    missing_x: usize,
+    others: &[&Vec<Field>],
+    f: &mut impl FnMut(&[Field]),
+) {
+    if has_triple(fields) {
+        return;
+    }
+    match empties.split_first() {
+        None => {
+            if missing_x == 0 && !others.iter().any(|other| **other == *fields) {
+                f(fields);
+            }
+        }
+        Some((pos, rest)) => {
+            if missing_x > 0 {
+                fields[*pos] = Field::X;
+                for_each_completion(fields, rest, missing_x - 1, others, f);
+            }
+            if missing_x < empties.len() {
+                fields[*pos] = Field::O;
+                for_each_completion(fields, rest, missing_x, others, f);
+            }
+            fields[*pos] = Field::Empty;
+        }
+    }
+}
+
+fn find_line_analysis(line: &Line, others: &[Line]) -> Option<Step> {
+    let half = line.fields.len() / 2;
+    let empties: Vec<usize> = (0..line.fields.len())
+        .filter(|i| Field::Empty == line.fields[*i])
+        .collect();
+    let count_x = line.count(Field::X);
+    if empties.is_empty() || count_x > half || line.count(Field::O) > half {
+        return None;
+    }
+    let complete: Vec<&Vec<Field>> = others
+        .iter()
+        .filter(|other| other.is_complete())
+        .map(|other| &other.fields)
+        .collect();
+
+    // common value of every empty field in all completions, `None` once they differ
+    let mut common: Option<Vec<Option<Field>>> = None;
+    let mut fields = line.fields.clone();
+    for_each_completion(
+        &mut fields,
+        &empties,
+        half - count_x,
+        &complete,
+        &mut |completion| match common.as_mut() {
+            None => {
+                common = Some(empties.iter().map(|i| Some(completion[*i])).collect());
+            }
+            Some(common) => {
+                for (value, i) in common.iter_mut().zip(empties.iter()) {
+                    if *value != Some(completion[*i]) {
+                        *value = None;
+                    }
+                }
+            }
+        },
+    );
+
+    let common = common?;
+    common
+        .iter()
+        .zip(empties.iter())
+        .find_map(|(value, i)| value.map(|field| line.step(*i, field, Technique::LineAnalysis)))
+}
+
+type FindStep = fn(&Line, &[Line]) -> Option<Step>;
+
+/// Returns the next field, which can be deduced from `board`.
+/// Easier techniques are preferred over harder ones.
+pub fn next_step(board: &Board) -> Option<Step> {
+    let size = board.get_size();
+    let lines = lines(board);
+    // rows are only compared with rows and columns with columns
+    let (rows, cols) = lines.split_at(size);
+
+    let techniques: [FindStep; 5] = [
+        find_pair,
+        find_no_triple,
+        find_balance,
+        find_uniqueness,
+        find_line_analysis,
+    ];
+    for technique in techniques.iter() {
+        for (i, line) in lines.iter().enumerate() {
+            let others = if i < size { rows } else { cols };
+            if let Some(step) = technique(line, others) {
+                return Some(step);
+            }
+        }
+    }
+    None
+}
+
+/// Solves `board` as far as possible with logical deduction only.
+pub fn solve(board: &Board) -> Solution {
+    let mut board = board.clone();
+    let mut steps = Vec::new();
+    while let Some(step) = next_step(&board) {
+        set_field(&mut board, step.col, step.row, step.field);
+        steps.push(step);
+    }
+    Solution { board, steps }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::generator::create_seeded_puzzle_board;
+    use binoxxo::rules::is_board_valid;
+    use std::str::FromStr;
+
+    fn step(board: &str) -> Option<Step> {
+        next_step(&Board::from_str(board).unwrap())
+    }
+
+    #[test]
+    fn line_analysis_technique() {
+        // an X in the last field of the first row would need O O O in the middle
+        let found = step(
+            "
+            X _ _ _ X _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _",
+        );
+        assert_eq!(
+            Some(Step {
+                col: 5,
+                row: 0,
+                field: Field::O,
+                technique: Technique::LineAnalysis
+            }),
+            found
+        );
+    }
+
+    #[test]
+    fn solve_puzzle() {
+        let puzzle = Board::from_str(
+            "
+            X _ O X
+            O X _ O
+            _ X O O
+            O O X _",
+        )
+        .unwrap();
+        let solution = solve(&puzzle);
+
+        assert!(solution.is_solved());
+        assert!(is_board_valid(&solution.board));
+        assert_eq!(4, solution.steps.len());
+        assert_eq!(Some(Technique::NoTriple), solution.hardest_technique());
+    }
+
+    #[test]
+    fn solved_generated_puzzles_are_valid() {
+        for seed in 0..5 {
+            let solution = solve(&create_seeded_puzzle_board(seed, 8, 10));
+            assert!(!solution.is_solved() || is_board_valid(&solution.board));
+        }
+    }
+
+    #[test]
+    fn solver_does_not_guess() {
+        let solution = solve(&Board::new(4));
+        assert!(!solution.is_solved());
+        assert!(solution.steps.is_empty());
+        assert_eq!(None, solution.hardest_technique());
+    }
+}
@@ -0,0 +1,221 @@
+//! Persisted log of started and won games and the statistics derived from it.
+
+use crate::model::Difficulty;
+use serde_derive::{Deserialize, Serialize};
+
+/// Oldest entries are dropped, when the log grows beyond this length.
+const MAX_LOG_ENTRIES: usize = 2000;
+
+/// Upper bounds of the buckets of the time histogram in ms.
+/// The last bucket takes all longer times.
+pub const TIME_BUCKETS: [u64; 5] = [60_000, 120_000, 300_000, 600_000, 1_200_000];
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum GameEvent {
+    Started,
+    Won {
+        time_ms: u64,
+        /// whether fields were revealed
+        #[serde(default)]
+        assisted: bool,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub difficulty: Difficulty,
+    pub size: usize,
+    pub event: GameEvent,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameLog {
+    entries: Vec<LogEntry>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub started: usize,
+    pub won: usize,
+    pub current_streak: usize,
+    pub best_streak: usize,
+    /// won games with revealed fields
+    pub assisted: usize,
+    /// number of won games per bucket of `TIME_BUCKETS`
+    pub histogram: [usize; TIME_BUCKETS.len() + 1],
+}
+
+impl Stats {
+    /// Returns the percentage of won games.
+    pub fn win_rate(&self) -> Option<f64> {
+        if self.started > 0 {
+            Some(100.0 * self.won as f64 / self.started as f64)
+        } else {
+            None
+        }
+    }
+}
+
+fn bucket(time_ms: u64) -> usize {
+    TIME_BUCKETS
+        .iter()
+        .position(|bound| time_ms < *bound)
+        .unwrap_or(TIME_BUCKETS.len())
+}
+
+impl GameLog {
+    fn push(&mut self, entry: LogEntry) {
+        self.entries.push(entry);
+        if self.entries.len() > MAX_LOG_ENTRIES {
+            let excess = self.entries.len() - MAX_LOG_ENTRIES;
+            self.entries.drain(..excess);
+        }
+    }
+
+    pub fn log_start(&mut self, difficulty: Difficulty, size: usize) {
+        self.push(LogEntry {
+            difficulty,
+            size,
+            event: GameEvent::Started,
+        });
+    }
+
+    pub fn log_win(&mut self, difficulty: Difficulty, size: usize, time_ms: u64, assisted: bool) {
+        self.push(LogEntry {
+            difficulty,
+            size,
+            event: GameEvent::Won { time_ms, assisted },
+        });
+    }
+
+    /// Returns the distinct board sizes in the log in ascending order.
+    pub fn sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = self.entries.iter().map(|e| e.size).collect();
+        sizes.sort_unstable();
+        sizes.dedup();
+        sizes
+    }
+
+    /// Returns the statistics of all games selected by `filter`.
+    /// A started game counts as lost, when another game is started before it is won.
+    pub fn stats(&self, filter: impl Fn(&LogEntry) -> bool) -> Stats {
+        let mut stats = Stats::default();
+        // outcome of the games in order, `None` for running or abandoned games
+        let mut outcomes: Vec<Option<(u64, bool)>> = Vec::new();
+        for entry in self.entries.iter().filter(|e| filter(e)) {
+            match entry.event {
+                GameEvent::Started => outcomes.push(None),
+                GameEvent::Won { time_ms, assisted } => match outcomes.last_mut() {
+                    Some(outcome @ None) => *outcome = Some((time_ms, assisted)),
+                    // won without logged start, e.g. a game from before the log existed
+                    _ => outcomes.push(Some((time_ms, assisted))),
+                },
+            }
+        }
+
+        let mut streak = 0;
+        for outcome in outcomes.iter() {
+            stats.started += 1;
+            match outcome {
+                Some((time_ms, assisted)) => {
+                    stats.won += 1;
+                    stats.assisted += usize::from(*assisted);
+                    stats.histogram[bucket(*time_ms)] += 1;
+                    streak += 1;
+                    stats.best_streak = stats.best_streak.max(streak);
+                }
+                None => streak = 0,
+            }
+        }
+        // the last game may still be running and does not break the streak
+        stats.current_streak = match outcomes.last() {
+            Some(None) => outcomes[..outcomes.len() - 1]
+                .iter()
+                .rev()
+                .take_while(|o| o.is_some())
+                .count(),
+            _ => streak,
+        };
+        stats
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn log(events: &[(Difficulty, Option<u64>)]) -> GameLog {
+        let mut log = GameLog::default();
+        for (difficulty, won) in events {
+            match won {
+                None => log.log_start(*difficulty, 6),
+                Some(time_ms) => log.log_win(*difficulty, 6, *time_ms, false),
+            }
+        }
+        log
+    }
+
+    #[test]
+    fn count_started_and_won_games() {
+        use Difficulty::*;
+        let log = log(&[
+            (Easy, None),
+            (Easy, Some(30_000)),
+            (Hard, None),
+            (Easy, None),
+            (Easy, None),
+            (Easy, Some(90_000)),
+        ]);
+
+        let all = log.stats(|_| true);
+        assert_eq!(4, all.started);
+        assert_eq!(2, all.won);
+        assert_eq!(Some(50.0), all.win_rate());
+        assert_eq!([1, 1, 0, 0, 0, 0], all.histogram);
+
+        let hard = log.stats(|e| e.difficulty == Hard);
+        assert_eq!(1, hard.started);
+        assert_eq!(0, hard.won);
+        assert_eq!(vec![6], log.sizes());
+    }
+
+    #[test]
+    fn streaks() {
+        use Difficulty::*;
+        let log = log(&[
+            (Easy, None),
+            (Easy, Some(1)),
+            (Easy, None),
+            (Easy, Some(1)),
+            (Easy, None),
+            (Easy, None),
+            (Easy, Some(1)),
+            (Easy, None),
+        ]);
+
+        let stats = log.stats(|_| true);
+        assert_eq!(2, stats.best_streak);
+        // the running last game does not break the streak
+        assert_eq!(1, stats.current_streak);
+    }
+
+    #[test]
+    fn count_assisted_games() {
+        let mut log = GameLog::default();
+        log.log_start(Difficulty::Easy, 6);
+        log.log_win(Difficulty::Easy, 6, 1, true);
+        log.log_start(Difficulty::Easy, 6);
+        log.log_win(Difficulty::Easy, 6, 1, false);
+
+        let stats = log.stats(|_| true);
+        assert_eq!(2, stats.won);
+        assert_eq!(1, stats.assisted);
+    }
+
+    #[test]
+    fn empty_log() {
+        let stats = GameLog::default().stats(|_| true);
+        assert_eq!(Stats::default(), stats);
+        assert_eq!(None, stats.win_rate());
+    }
+}
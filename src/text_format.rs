@@ -0,0 +1,188 @@
+//! Plain text format for importing and exporting puzzles.
+//!
+//! A puzzle is a grid with one line per row. `X` and `O` are givens,
+//! `.` or `_` are empty fields. Whitespace within a line is ignored,
+//! so the output of `Board::to_string` can be imported as well.
+
+use crate::model::{breaks_rules, set_field, MAX_SIZE, MIN_SIZE};
+use crate::solutions::{count_solutions, SolutionCount};
+use binoxxo::field::{Board, Field};
+
+/// State of the import and export dialog.
+#[derive(Clone, Debug, Default)]
+pub struct TransferState {
+    pub visible: bool,
+    pub import_text: String,
+    pub error: Option<ImportError>,
+    /// whether the last copy to the clipboard succeeded, `None` before copying
+    pub copied: Option<bool>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImportError {
+    Empty,
+    /// unknown character with 1-based row and column
+    InvalidChar {
+        ch: char,
+        row: usize,
+        col: usize,
+    },
+    /// 1-based row with a length differing from the number of rows
+    RowLength {
+        row: usize,
+        length: usize,
+        size: usize,
+    },
+    InvalidSize(usize),
+    BreaksRules,
+    NoSolution,
+    /// more than one solution, so the givens do not define the puzzle
+    Ambiguous,
+}
+
+impl ImportError {
+    /// Returns the id of the translated error message.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            ImportError::Empty => "import-error-empty",
+            ImportError::InvalidChar { .. } => "import-error-char",
+            ImportError::RowLength { .. } => "import-error-row-length",
+            ImportError::InvalidSize(_) => "import-error-size",
+            ImportError::BreaksRules => "import-error-rules",
+            ImportError::NoSolution => "import-error-no-solution",
+            ImportError::Ambiguous => "import-error-ambiguous",
+        }
+    }
+}
+
+/// Returns `givens` as text grid.
+pub fn format_puzzle(givens: &Board) -> String {
+    let size = givens.get_size();
+    let mut text = String::new();
+    for row in 0..size {
+        let fields: Vec<&str> = (0..size)
+            .map(|col| match givens.get(col, row) {
+                Field::X => "X",
+                Field::O => "O",
+                Field::Empty => ".",
+            })
+            .collect();
+        text += &fields.join(" ");
+        text.push('\n');
+    }
+    text
+}
+
+/// Parses a text grid into the givens of a puzzle.
+pub fn parse_puzzle(text: &str) -> Result<Board, ImportError> {
+    let mut rows = Vec::new();
+    for (row, line) in text.lines().filter(|l| !l.trim().is_empty()).enumerate() {
+        let fields = line
+            .chars()
+            .filter(|ch| !ch.is_whitespace())
+            .enumerate()
+            .map(|(col, ch)| match ch {
+                'X' | 'x' => Ok(Field::X),
+                'O' | 'o' => Ok(Field::O),
+                '.' | '_' => Ok(Field::Empty),
+                _ => Err(ImportError::InvalidChar {
+                    ch,
+                    row: row + 1,
+                    col: col + 1,
+                }),
+            })
+            .collect::<Result<Vec<Field>, ImportError>>()?;
+        rows.push(fields);
+    }
+
+    let size = rows.len();
+    if size == 0 {
+        return Err(ImportError::Empty);
+    }
+    if let Some((row, fields)) = rows.iter().enumerate().find(|(_, f)| f.len() != size) {
+        return Err(ImportError::RowLength {
+            row: row + 1,
+            length: fields.len(),
+            size,
+        });
+    }
+    if !(MIN_SIZE..=MAX_SIZE).contains(&size) || size % 2 == 1 {
+        return Err(ImportError::InvalidSize(size));
+    }
+
+    let mut board = Board::new(size);
+    for (row, fields) in rows.iter().enumerate() {
+        for (col, field) in fields.iter().enumerate() {
+            set_field(&mut board, col, row, *field);
+        }
+    }
+    if breaks_rules(&board) {
+        return Err(ImportError::BreaksRules);
+    }
+    match count_solutions(&board) {
+        SolutionCount::NoSolution => Err(ImportError::NoSolution),
+        SolutionCount::Multiple => Err(ImportError::Ambiguous),
+        SolutionCount::Unique => Ok(board),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    const PUZZLE: &str = "X X . .\n. . O .\n. . . .\n. X . .\n";
+
+    #[test]
+    fn export_and_import() {
+        let board = parse_puzzle(PUZZLE).unwrap();
+        assert_eq!(Field::X, board.get(0, 0));
+        assert_eq!(Field::O, board.get(2, 1));
+        assert_eq!(Field::Empty, board.get(3, 3));
+        assert_eq!(PUZZLE, format_puzzle(&board));
+    }
+
+    #[test]
+    fn import_board_to_string() {
+        let board = Board::from_str("X X _ _\n_ _ O _\n_ _ _ _\n_ X _ _").unwrap();
+        assert_eq!(Ok(board.clone()), parse_puzzle(&board.to_string()));
+        assert_eq!(Ok(board), parse_puzzle("xx..\n..o.\n\n....\n.x..\n"));
+    }
+
+    #[test]
+    fn reject_invalid_text() {
+        assert_eq!(Err(ImportError::Empty), parse_puzzle(" \n"));
+        assert_eq!(
+            Err(ImportError::InvalidChar {
+                ch: '?',
+                row: 2,
+                col: 3
+            }),
+            parse_puzzle("X...\n..?.\n....\n....")
+        );
+        assert_eq!(
+            Err(ImportError::RowLength {
+                row: 3,
+                length: 3,
+                size: 4
+            }),
+            parse_puzzle("X...\n....\n...\n....")
+        );
+        assert_eq!(
+            Err(ImportError::InvalidSize(3)),
+            parse_puzzle("X..\n...\n...")
+        );
+        assert_eq!(
+            Err(ImportError::BreaksRules),
+            parse_puzzle("XXX.\n....\n....\n....")
+        );
+        assert_eq!(
+            Err(ImportError::NoSolution),
+            parse_puzzle("XX..\n....\nXX..\n....")
+        );
+        assert_eq!(
+            Err(ImportError::Ambiguous),
+            parse_puzzle("X...\n....\n....\n....")
+        );
+    }
+}
@@ -0,0 +1,91 @@
+/// Measures the play time of a game.
+/// All timestamps are milliseconds as returned by `js_sys::Date::now`,
+/// so the timer itself does not depend on the browser.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Timer {
+    elapsed_ms: f64,
+    running_since: Option<f64>,
+}
+
+impl Timer {
+    /// Returns a stopped timer, which already measured `elapsed_ms`.
+    pub fn with_elapsed(elapsed_ms: f64) -> Timer {
+        Timer {
+            elapsed_ms,
+            running_since: None,
+        }
+    }
+
+    /// Starts (or continues) measuring at time `now`.
+    pub fn start(&mut self, now: f64) {
+        if self.running_since.is_none() {
+            self.running_since = Some(now);
+        }
+    }
+
+    /// Stops measuring at time `now` and keeps the elapsed time.
+    pub fn pause(&mut self, now: f64) {
+        self.elapsed_ms = self.elapsed_ms(now);
+        self.running_since = None;
+    }
+
+    pub fn elapsed_ms(&self, now: f64) -> f64 {
+        match self.running_since {
+            Some(since) => self.elapsed_ms + (now - since).max(0.0),
+            None => self.elapsed_ms,
+        }
+    }
+}
+
+/// Formats `ms` as `m:ss` or `h:mm:ss`.
+pub fn format_time(ms: u64) -> String {
+    let secs = ms / 1000;
+    let (hours, minutes, seconds) = (secs / 3600, (secs / 60) % 60, secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn measures_from_start() {
+        let mut timer = Timer::default();
+        assert_eq!(0.0, timer.elapsed_ms(100.0));
+
+        timer.start(100.0);
+        assert_eq!(250.0, timer.elapsed_ms(350.0));
+    }
+
+    #[test]
+    fn continues_restored_time() {
+        let mut timer = Timer::with_elapsed(1000.0);
+        assert_eq!(1000.0, timer.elapsed_ms(5000.0));
+
+        timer.start(5000.0);
+        timer.start(6000.0);
+        assert_eq!(3000.0, timer.elapsed_ms(7000.0));
+    }
+
+    #[test]
+    fn pause_and_resume() {
+        let mut timer = Timer::default();
+        timer.start(0.0);
+        timer.pause(500.0);
+        assert_eq!(500.0, timer.elapsed_ms(10_000.0));
+
+        timer.start(10_000.0);
+        assert_eq!(700.0, timer.elapsed_ms(10_200.0));
+    }
+
+    #[test]
+    fn format_times() {
+        assert_eq!("0:00", format_time(999));
+        assert_eq!("1:05", format_time(65_000));
+        assert_eq!("1:00:01", format_time(3_601_000));
+    }
+}
@@ -1,57 +1,119 @@
-use crate::control::{CellPos, Message};
+use crate::check::CheckState;
+use crate::conflicts::{Conflict, Conflicts};
+use crate::control::{CellPos, Message, Reveal};
+use crate::hint::{message_id, HintState};
+use crate::lang::Diagnostic;
 use crate::model::*;
+use crate::solutions::SolutionCount;
+use crate::solver::Technique;
+use crate::stats::{Stats, TIME_BUCKETS};
+use crate::text_format::{format_puzzle, ImportError};
+use crate::timer::format_time;
 use binoxxo::field::Field;
-use binoxxo::rules::{is_board_full, is_board_valid, is_move_valid};
+use binoxxo::rules::{is_board_full, is_board_valid};
 use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
 use seed::{prelude::*, *};
-use web_sys::console::log_1;
 
 struct ViewBuilder<'a> {
     bundle: FluentBundle<&'a FluentResource>,
     model: &'a Model,
+    /// renders the symbol of a field
+    icon: fn(Field) -> Vec<Node<Message>>,
+    /// conflicts to highlight, computed once per render
+    conflicts: Conflicts,
 }
 
 impl<'a> ViewBuilder<'a> {
+    /// Returns the translated message, or the id if it is missing.
+    /// Problems are recorded in the diagnostics instead of stopping the app.
     fn tr_with_args(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        let value = match self.bundle.get_message(id).map(|msg| msg.value()) {
+            Some(Some(value)) => value,
+            Some(None) => return self.report(id, "message has no value".to_string()),
+            None => return self.report(id, "message is missing".to_string()),
+        };
         let mut errors = vec![];
-        let msg = self
-            .bundle
-            .get_message(id)
-            .expect("Failed to retrieve the message");
-        let value = msg
-            .value()
-            .expect("Failed to retrieve the value of the message");
         let res = self
             .bundle
             .format_pattern(value, args, &mut errors)
             .to_string();
-        if !errors.is_empty() {
-            log_1(&format!("Failed to translate {}:\n  {:?}\n", id, errors).into());
-            panic!("Error in fluent");
+        for error in errors {
+            self.report(id, error.to_string());
         }
         res
     }
 
+    /// Records a problem with message `id` and returns the id as fallback text.
+    fn report(&self, id: &str, problem: String) -> String {
+        self.model.res_mgr.report(Diagnostic {
+            locale: self.model.language.to_string(),
+            id: id.to_string(),
+            problem,
+        });
+        id.to_string()
+    }
+
     fn tr(&self, id: &str) -> String {
         self.tr_with_args(id, None)
     }
 
-    fn view_field(&self, field: Field) -> Vec<Node<Message>> {
-        match field {
-            Field::Empty => {
-                Node::<Message>::from_html(None, include_str!("../assets/icons/dot-opt.svg"))
-            }
-            Field::X => Node::<Message>::from_html(None, include_str!("../assets/icons/x-opt.svg")),
-            Field::O => Node::<Message>::from_html(None, include_str!("../assets/icons/o-opt.svg")),
+    /// Shows the pencil marks instead of the dot of an empty field.
+    fn view_field(&self, field: Field, marks: Marks) -> Vec<Node<Message>> {
+        if Field::Empty != field || marks.is_empty() {
+            return (self.icon)(field);
         }
+        nodes![span![
+            C!["marks"],
+            IF!(marks.x => span!["X"]),
+            IF!(marks.o => span!["O"]),
+        ]]
+    }
+
+    /// Returns the label read by screen readers, e.g. "row 3, column 5, X, given".
+    fn cell_label(&self, col: usize, row: usize, is_valid: bool) -> String {
+        let mut args = FluentArgs::new();
+        args.set("row", FluentValue::from(row + 1));
+        args.set("col", FluentValue::from(col + 1));
+        args.set(
+            "field",
+            FluentValue::from(self.tr(&field_id(self.model.board.get(col, row)))),
+        );
+        let kind = if !self.model.editable.is_editable(col, row) {
+            "cell-given"
+        } else if self.model.revealed.contains(&CellPos { col, row }) {
+            "cell-revealed"
+        } else {
+            "cell-guess"
+        };
+        args.set("kind", FluentValue::from(self.tr(kind)));
+        let label = self.tr_with_args("cell-label", Some(&args));
+        if is_valid {
+            label
+        } else {
+            format!("{}, {}", label, self.tr("cell-error"))
+        }
+    }
+
+    /// Returns the tooltip explaining the broken rules and whether checking found the field wrong.
+    fn conflict_title(&self, conflicts: &[Conflict], is_wrong: bool) -> String {
+        let texts: Vec<String> = conflicts
+            .iter()
+            .map(|conflict| self.tr(conflict.message_id()))
+            .chain(IF!(is_wrong => self.tr("check-wrong-field")))
+            .collect();
+        texts.join("\n")
     }
 
     fn view_cell(&self, col: usize, row: usize) -> Node<Message> {
         let field = self.model.board.get(col, row);
         let editable = self.model.editable.is_editable(col, row);
-        let is_valid = (Helper::Disabled == self.model.helper)
-            || (Field::Empty == field)
-            || is_move_valid(&self.model.board, col, row);
+        let conflicts = self.conflicts.at(col, row);
+        let is_wrong = self.model.check.is_wrong_at(col, row);
+        let is_valid = conflicts.is_empty() && !is_wrong;
+        let is_hint = self.model.hint.is_hint_at(col, row);
+        let is_cursor = self.model.cursor == Some(CellPos { col, row });
+        let is_selected = self.model.selected == Some(CellPos { col, row });
+        let is_revealed = self.model.revealed.contains(&CellPos { col, row });
         let cell_id = format!("cell-{}-{}", col, row);
         let size = self.model.get_size();
 
@@ -59,26 +121,85 @@ impl<'a> ViewBuilder<'a> {
             // id is required by engine for correct updates,
             // otherwise "board" gets randomized in NewGame (bug in seed?)
             id!(&cell_id),
-            C![IF!(editable => "guess"), IF!(not(is_valid) => "error")],
+            C![
+                IF!(editable && !is_revealed && !self.model.editing => "guess"),
+                IF!(self.model.editing => "design"),
+                conflicts.iter().map(Conflict::class).collect::<Vec<_>>(),
+                IF!(is_wrong => "wrong"),
+                IF!(is_revealed => "revealed"),
+                IF!(is_hint => "hint"),
+                IF!(is_cursor => "cursor"),
+                IF!(is_selected && !is_cursor => "selected")
+            ],
+            attrs! {
+                At::from("role") => "gridcell",
+                At::AriaLabel => self.cell_label(col, row, is_valid),
+                At::from("aria-readonly") => (!editable).to_string(),
+                At::from("aria-invalid") => (!is_valid).to_string(),
+                At::from("aria-selected") => is_selected.to_string(),
+            },
+            IF!(not(is_valid) => attrs! {At::Title => self.conflict_title(conflicts, is_wrong)}),
             style! {St::Width => format!("{}%", 100.0 / (size as f64))},
-            self.view_field(field),
+            span![
+                attrs! {At::AriaHidden => true},
+                self.view_field(field, self.model.notes.get(col, row))
+            ],
             IF!(editable => ev(Ev::Click, move |_| { Message::Toggle(CellPos { col, row }) }))
         ]
     }
 
-    fn view_row(&self, row: usize) -> Node<Message> {
+    /// Shows how many `X` and `O` a line contains. Screen readers get the fields instead.
+    fn view_line_count(&self, count: Option<LineCount>) -> Node<Message> {
+        let size = self.model.get_size();
+        let count = match count {
+            Some(count) => count,
+            None => return td![C!["count"], attrs! {At::AriaHidden => true}],
+        };
+        let view_symbol = |symbol: &str, number: usize| {
+            span![
+                C![IF!(number >= size / 2 => "complete")],
+                format!("{}{}", number, symbol)
+            ]
+        };
+        td![
+            C!["count", IF!(count.is_complete(size) => "complete")],
+            attrs! {At::AriaHidden => true},
+            view_symbol("X", count.x),
+            " ",
+            view_symbol("O", count.o),
+        ]
+    }
+
+    fn view_row(&self, row: usize, count: Option<LineCount>) -> Node<Message> {
         let size = self.model.get_size();
         let cells: Vec<Node<Message>> = (0..size).map(|col| self.view_cell(col, row)).collect();
-        tr![cells]
+        tr![
+            attrs! {At::from("role") => "row"},
+            cells,
+            count.map(|count| self.view_line_count(Some(count))),
+        ]
+    }
+
+    /// Returns the row below the board, which counts the fields of every column.
+    fn view_column_counts(&self) -> Node<Message> {
+        let counts = LineCount::columns(&self.model.board);
+        tr![
+            C!["counts"],
+            attrs! {At::AriaHidden => true},
+            counts
+                .into_iter()
+                .map(|count| self.view_line_count(Some(count))),
+            self.view_line_count(None),
+        ]
     }
 
     fn view_difficulty(&self, difficulty: Difficulty) -> Node<Message> {
-        a![
+        button![
             C!["dropdown-item"],
             attrs! {
-                At::Href => "#";
+                At::Type => "button";
             },
-            self.tr(&format!("difficulty-{}", difficulty)),
+            self.tr(&difficulty_id(difficulty)),
             ev(Ev::Click, move |_| { Message::NewGame(difficulty) })
         ]
     }
@@ -104,6 +225,23 @@ impl<'a> ViewBuilder<'a> {
                 self.view_difficulty(Difficulty::Easy),
                 self.view_difficulty(Difficulty::Medium),
                 self.view_difficulty(Difficulty::Hard),
+                li![hr![C!["dropdown-divider"]]],
+                button![
+                    C!["dropdown-item"],
+                    attrs! {
+                        At::Type => "button";
+                    },
+                    self.tr("daily-puzzle"),
+                    ev(Ev::Click, |_| { Message::NewDailyGame })
+                ],
+                button![
+                    C!["dropdown-item"],
+                    attrs! {
+                        At::Type => "button";
+                    },
+                    self.tr("custom-game"),
+                    ev(Ev::Click, |_| { Message::ToggleCustomGame })
+                ],
             ]
         ];
         let new_game_levels = div![
@@ -136,91 +274,780 @@ impl<'a> ViewBuilder<'a> {
             C!["dropdown"],
             new_game_button,
             new_game_levels,
-            raw!("&nbsp;"),
+            "\u{a0}",
             enable_helper
         ]
     }
 
-    fn view_board(&self, board_id: &str, is_error: bool) -> Node<Message> {
+    fn view_custom_game_form(&self) -> Node<Message> {
+        if !self.model.show_custom {
+            return seed::empty();
+        }
+        let custom = self.model.custom;
+        let sizes = CustomGame::sizes().map(|size| {
+            option![
+                attrs! {
+                    At::Value => size,
+                    At::Selected => (size == custom.size).as_at_value(),
+                },
+                format!("{}×{}", size, size)
+            ]
+        });
+        let difficulties = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard]
+            .iter()
+            .map(|difficulty| {
+                option![
+                    attrs! {
+                        At::Value => difficulty,
+                        At::Selected => (*difficulty == custom.difficulty).as_at_value(),
+                    },
+                    self.tr(&difficulty_id(*difficulty))
+                ]
+            });
+
+        div![
+            id!("custom-game"),
+            C!["card card-body"],
+            div![
+                C!["mb-2"],
+                label![
+                    C!["form-label"],
+                    attrs! {At::For => "custom-size"},
+                    self.tr("custom-size")
+                ],
+                select![
+                    id!("custom-size"),
+                    C!["form-select"],
+                    sizes,
+                    input_ev(Ev::Change, |value| value
+                        .parse()
+                        .ok()
+                        .map(Message::SetCustomSize))
+                ],
+            ],
+            div![
+                C!["mb-2"],
+                label![
+                    C!["form-label"],
+                    attrs! {At::For => "custom-difficulty"},
+                    self.tr("custom-difficulty")
+                ],
+                select![
+                    id!("custom-difficulty"),
+                    C!["form-select"],
+                    difficulties,
+                    input_ev(Ev::Change, |value| {
+                        [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard]
+                            .into_iter()
+                            .find(|difficulty| difficulty.to_string() == value)
+                            .map(Message::SetCustomDifficulty)
+                    })
+                ],
+            ],
+            button![
+                C!["btn btn-primary"],
+                id!("start-custom-game"),
+                self.tr("custom-start"),
+                ev(Ev::Click, |_| { Message::NewCustomGame })
+            ],
+            "\u{a0}",
+            button![
+                C!["btn btn-outline-primary"],
+                id!("design-puzzle"),
+                self.tr("design-puzzle"),
+                ev(Ev::Click, |_| { Message::NewEditor })
+            ],
+        ]
+    }
+
+    fn view_editor(&self) -> Node<Message> {
+        if !self.model.editing {
+            return seed::empty();
+        }
+        let (class, text) = match self.model.solution_count {
+            SolutionCount::NoSolution => ("alert alert-danger", "editor-no-solution"),
+            SolutionCount::Unique => ("alert alert-success", "editor-unique"),
+            SolutionCount::Multiple => ("alert alert-warning", "editor-multiple"),
+        };
+        let solvable = SolutionCount::NoSolution != self.model.solution_count;
+        div![
+            id!("editor"),
+            div![
+                id!("editor-status"),
+                C![class],
+                attrs! {"role" => "status"},
+                self.tr(text)
+            ],
+            button![
+                C!["btn btn-primary"],
+                id!("play-design"),
+                attrs! {At::Disabled => (!solvable).as_at_value()},
+                self.tr("editor-play"),
+                ev(Ev::Click, |_| { Message::PlayDesign })
+            ],
+            "\u{a0}",
+            button![
+                C!["btn btn-outline-secondary"],
+                id!("export-design"),
+                self.tr("editor-export"),
+                ev(Ev::Click, |_| { Message::ToggleTransfer })
+            ],
+        ]
+    }
+
+    /// Warns about designed or linked puzzles without a unique solution.
+    fn view_ambiguous_warning(&self) -> Node<Message> {
+        if self.model.editing
+            || self.model.waiting
+            || SolutionCount::Multiple != self.model.solution_count
+        {
+            return seed::empty();
+        }
+        div![
+            id!("ambiguous-warning"),
+            C!["alert alert-warning"],
+            attrs! {"role" => "alert"},
+            self.tr("puzzle-ambiguous")
+        ]
+    }
+
+    fn view_history_buttons(&self) -> Vec<Node<Message>> {
+        nodes![
+            button![
+                C!["btn btn-outline-secondary"],
+                id!("undo-move"),
+                attrs! {At::Disabled => (!self.model.history.can_undo()).as_at_value()},
+                self.tr("undo"),
+                ev(Ev::Click, |_| { Message::Undo })
+            ],
+            Node::new_text("\u{a0}"),
+            button![
+                C!["btn btn-outline-secondary"],
+                id!("redo-move"),
+                attrs! {At::Disabled => (!self.model.history.can_redo()).as_at_value()},
+                self.tr("redo"),
+                ev(Ev::Click, |_| { Message::Redo })
+            ],
+        ]
+    }
+
+    fn view_note_mode_button(&self) -> Node<Message> {
+        if self.model.editing {
+            return seed::empty();
+        }
+        let note_mode = self.model.note_mode;
+        button![
+            C![if note_mode {
+                "btn btn-secondary"
+            } else {
+                "btn btn-outline-secondary"
+            }],
+            id!("note-mode"),
+            attrs! {
+                At::Title => self.tr("notes-tooltip"),
+                At::AriaPressed => note_mode.to_string(),
+            },
+            self.tr(if note_mode { "notes-on" } else { "notes-off" }),
+            ev(Ev::Click, |_| { Message::ToggleNoteMode })
+        ]
+    }
+
+    fn view_hint(&self) -> Node<Message> {
+        let text = match self.model.hint {
+            HintState::Hidden => return seed::empty(),
+            HintState::Found(step) => {
+                let mut args = FluentArgs::new();
+                args.set("field", FluentValue::from(format!("{:?}", step.field)));
+                self.tr_with_args(message_id(step.technique), Some(&args))
+            }
+            HintState::NotFound => self.tr("hint-none"),
+        };
+        div![
+            id!("hint-text"),
+            C!["alert alert-info"],
+            attrs! {"role" => "status"},
+            text
+        ]
+    }
+
+    fn view_check(&self) -> Node<Message> {
+        if self.model.editing {
+            return seed::empty();
+        }
+        let mut args = FluentArgs::new();
+        let (class, text) = match &self.model.check {
+            CheckState::Hidden => ("", None),
+            CheckState::Correct => ("alert alert-success", Some(self.tr("check-correct"))),
+            CheckState::Wrong(wrong) => {
+                args.set("count", FluentValue::from(wrong.len()));
+                (
+                    "alert alert-danger",
+                    Some(self.tr_with_args("check-wrong", Some(&args))),
+                )
+            }
+            CheckState::Unavailable => ("alert alert-warning", Some(self.tr("check-unavailable"))),
+        };
+        let mut mistake_args = FluentArgs::new();
+        mistake_args.set("count", FluentValue::from(self.model.mistakes));
+        div![
+            id!("check-result"),
+            text.map(|text| div![C![class], attrs! {"role" => "status"}, text]),
+            p![
+                id!("mistakes"),
+                self.tr_with_args("mistakes", Some(&mistake_args))
+            ],
+        ]
+    }
+
+    fn view_reveal_buttons(&self) -> Node<Message> {
+        if self.model.editing {
+            return seed::empty();
+        }
+        let cell = self.model.selected.filter(|pos| {
+            self.model.editable.is_editable(pos.col, pos.row) && !self.model.revealed.contains(pos)
+        });
+        div![
+            id!("reveal"),
+            button![
+                C!["btn btn-outline-warning"],
+                id!("reveal-cell"),
+                attrs! {
+                    At::Title => self.tr("reveal-cell-tooltip"),
+                    At::Disabled => cell.is_none().as_at_value(),
+                },
+                self.tr("reveal-cell"),
+                cell.map(|pos| ev(Ev::Click, move |_| {
+                    Message::AskReveal(Reveal::Cell(pos))
+                }))
+            ],
+            "\u{a0}",
+            button![
+                C!["btn btn-outline-warning"],
+                id!("reveal-solution"),
+                self.tr("reveal-solution"),
+                ev(Ev::Click, |_| { Message::AskReveal(Reveal::Solution) })
+            ],
+            self.view_reveal_confirmation(),
+        ]
+    }
+
+    fn view_reveal_confirmation(&self) -> Node<Message> {
+        let question = match self.model.pending_reveal {
+            None => return seed::empty(),
+            Some(Reveal::Cell(_)) => self.tr("reveal-cell-confirm"),
+            Some(Reveal::Solution) => self.tr("reveal-solution-confirm"),
+        };
+        div![
+            id!("reveal-confirmation"),
+            C!["alert alert-warning"],
+            attrs! {"role" => "alertdialog"},
+            p![question],
+            button![
+                C!["btn btn-warning"],
+                id!("confirm-reveal"),
+                self.tr("reveal-confirm"),
+                ev(Ev::Click, |_| { Message::ConfirmReveal })
+            ],
+            "\u{a0}",
+            button![
+                C!["btn btn-outline-secondary"],
+                id!("cancel-reveal"),
+                self.tr("reveal-cancel"),
+                ev(Ev::Click, |_| { Message::CancelReveal })
+            ],
+        ]
+    }
+
+    fn view_board(&self, board_id: &str, is_error: bool, with_counts: bool) -> Node<Message> {
         let size = self.model.get_size();
-        let rows: Vec<Node<Message>> = (0..size).map(|row| self.view_row(row)).collect();
+        let row_counts = LineCount::rows(&self.model.board);
+        let rows: Vec<Node<Message>> = (0..size)
+            .map(|row| self.view_row(row, IF!(with_counts => row_counts[row])))
+            .collect();
+        let mut label_args = FluentArgs::new();
+        label_args.set("size", FluentValue::from(size));
         div![
             id!(board_id),
             C!["board"],
-            table![C![if is_error { "error" } else { "" }], rows,]
+            table![
+                C![
+                    if is_error { "error" } else { "" },
+                    IF!(size > 10 => "large")
+                ],
+                attrs! {
+                    At::from("role") => "grid",
+                    At::AriaLabel => self.tr_with_args("board-label", Some(&label_args)),
+                    At::from("aria-rowcount") => size,
+                    At::from("aria-colcount") => size,
+                },
+                rows,
+                IF!(with_counts => self.view_column_counts()),
+            ]
         ]
     }
 
+    /// Returns the text of the live region, which screen readers announce on changes.
+    fn announcement(&self, is_full: bool, is_valid: bool) -> String {
+        if is_valid {
+            return self.tr("announce-won");
+        }
+        if is_full {
+            return self.tr("announce-invalid");
+        }
+        let errors = self.conflicts.count();
+        if errors == 0 {
+            return String::new();
+        }
+        let mut args = FluentArgs::new();
+        args.set("count", FluentValue::from(errors));
+        self.tr_with_args("announce-errors", Some(&args))
+    }
+
     fn view_new_game_button_success_page(&self, difficulty: Difficulty) -> Node<Message> {
         button![
             C!["btn btn-primary"],
-            self.tr(&format!("difficulty-{}", difficulty)),
+            self.tr(&difficulty_id(difficulty)),
             ev(Ev::Click, move |_| { Message::NewGame(difficulty) })
         ]
     }
 
+    fn view_result(&self) -> Node<Message> {
+        let result = match self.model.result {
+            Some(result) => result,
+            None => return seed::empty(),
+        };
+        let stats = self.model.records.get(self.model.difficulty);
+
+        let mut time_arg = FluentArgs::new();
+        time_arg.set("time", FluentValue::from(format_time(result.time_ms)));
+        let mut stats_args = FluentArgs::new();
+        stats_args.set(
+            "best",
+            FluentValue::from(stats.best_ms.map(format_time).unwrap_or_default()),
+        );
+        stats_args.set(
+            "average",
+            FluentValue::from(stats.average_ms().map(format_time).unwrap_or_default()),
+        );
+        stats_args.set("count", FluentValue::from(stats.count));
+        let mut mistake_args = FluentArgs::new();
+        mistake_args.set("count", FluentValue::from(self.model.mistakes));
+        let mut revealed_args = FluentArgs::new();
+        revealed_args.set("count", FluentValue::from(self.model.revealed.len()));
+
+        div![
+            id!("game-result"),
+            h4![self.tr_with_args("game-time", Some(&time_arg))],
+            IF!(result.new_record => h4![C!["new-record"], self.tr("new-record")]),
+            p![self.tr_with_args("mistakes", Some(&mistake_args))],
+            IF!(!self.model.revealed.is_empty() => p![self.tr_with_args("revealed-count", Some(&revealed_args))]),
+            IF!(stats.count > 0 && !self.model.is_custom => p![self.tr_with_args("time-stats", Some(&stats_args))]),
+        ]
+    }
+
     fn view_success_alert(&self) -> Node<Message> {
         div![
             C!["alert alert-success"],
             attrs! {"role" => "alert"},
             h1![C!["alert-heading"], self.tr("game-won")],
+            self.view_result(),
             hr![],
             div![
                 C!["centered mx-auto"],
-                self.view_board("board-success", /*is_error:*/ false)
+                self.view_board(
+                    "board-success",
+                    /*is_error:*/ false,
+                    /*with_counts:*/ false
+                )
             ],
             hr![],
             h4![C!["text-center"], self.tr("new-game")],
             div![
                 C!["text-center mx-auto"],
                 self.view_new_game_button_success_page(Difficulty::Easy),
-                raw!["&nbsp;"],
+                "\u{a0}",
                 self.view_new_game_button_success_page(Difficulty::Medium),
-                raw!["&nbsp;"],
+                "\u{a0}",
                 self.view_new_game_button_success_page(Difficulty::Hard),
             ]
         ]
     }
 
-    fn view_success_page(&self) -> Node<Message> {
+    fn view_overlay(&self, page_id: &str, content: Node<Message>) -> Node<Message> {
         div![
-            id!("success-page"),
+            id!(page_id),
             C!("overlay"),
             div![
                 C!("overlay-content"),
                 div![
                     C!["container"],
-                    div![
-                        C!["row justify-content-center"],
-                        div![C!["col"], self.view_success_alert()]
-                    ]
+                    div![C!["row justify-content-center"], div![C!["col"], content]]
                 ]
             ]
         ]
     }
 
+    fn view_success_page(&self) -> Node<Message> {
+        self.view_overlay("success-page", self.view_success_alert())
+    }
+
+    fn view_stats_row(&self, label: String, stats: &Stats) -> Node<Message> {
+        let win_rate = match stats.win_rate() {
+            Some(rate) => format!("{:.0}%", rate),
+            None => "-".to_string(),
+        };
+        tr![
+            th![label],
+            td![stats.started.to_string()],
+            td![stats.won.to_string()],
+            td![win_rate],
+            td![stats.current_streak.to_string()],
+            td![stats.best_streak.to_string()],
+            td![stats.assisted.to_string()],
+            stats.histogram.iter().map(|count| td![count.to_string()]),
+        ]
+    }
+
+    fn view_statistics_page(&self) -> Node<Message> {
+        let log = &self.model.game_log;
+        let bucket_labels = TIME_BUCKETS
+            .iter()
+            .map(|bound| format!("< {}", format_time(*bound)))
+            .chain(
+                TIME_BUCKETS
+                    .last()
+                    .map(|bound| format!("≥ {}", format_time(*bound))),
+            );
+        let difficulty_rows = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard]
+            .iter()
+            .map(|difficulty| {
+                self.view_stats_row(
+                    self.tr(&difficulty_id(*difficulty)),
+                    &log.stats(|e| e.difficulty == *difficulty),
+                )
+            });
+        let size_rows = log.sizes().into_iter().map(|size| {
+            self.view_stats_row(format!("{}×{}", size, size), &log.stats(|e| e.size == size))
+        });
+
+        let content = div![
+            C!["alert alert-light"],
+            h1![C!["alert-heading"], self.tr("stats-header")],
+            div![
+                C!["table-responsive"],
+                table![
+                    C!["table table-sm"],
+                    thead![
+                        tr![
+                            th![attrs! {At::RowSpan => 2}],
+                            th![attrs! {At::RowSpan => 2}, self.tr("stats-started")],
+                            th![attrs! {At::RowSpan => 2}, self.tr("stats-won")],
+                            th![attrs! {At::RowSpan => 2}, self.tr("stats-win-rate")],
+                            th![attrs! {At::RowSpan => 2}, self.tr("stats-current-streak")],
+                            th![attrs! {At::RowSpan => 2}, self.tr("stats-best-streak")],
+                            th![attrs! {At::RowSpan => 2}, self.tr("stats-assisted")],
+                            th![
+                                attrs! {At::ColSpan => TIME_BUCKETS.len() + 1},
+                                self.tr("stats-times")
+                            ],
+                        ],
+                        tr![bucket_labels.map(|label| th![label])],
+                    ],
+                    tbody![difficulty_rows, size_rows],
+                ]
+            ],
+            button![
+                C!["btn btn-primary"],
+                id!("close-statistics"),
+                self.tr("close"),
+                ev(Ev::Click, |_| { Message::ToggleStatistics })
+            ],
+        ];
+        self.view_overlay("statistics-page", content)
+    }
+
+    fn view_import_error(&self, error: ImportError) -> Node<Message> {
+        let mut args = FluentArgs::new();
+        match error {
+            ImportError::InvalidChar { ch, row, col } => {
+                args.set("char", FluentValue::from(ch.to_string()));
+                args.set("row", FluentValue::from(row));
+                args.set("col", FluentValue::from(col));
+            }
+            ImportError::RowLength { row, length, size } => {
+                args.set("row", FluentValue::from(row));
+                args.set("length", FluentValue::from(length));
+                args.set("size", FluentValue::from(size));
+            }
+            ImportError::InvalidSize(size) => args.set("size", FluentValue::from(size)),
+            ImportError::Empty
+            | ImportError::BreaksRules
+            | ImportError::NoSolution
+            | ImportError::Ambiguous => (),
+        }
+        div![
+            id!("import-error"),
+            C!["alert alert-danger"],
+            attrs! {"role" => "alert"},
+            self.tr_with_args(error.message_id(), Some(&args))
+        ]
+    }
+
+    fn view_transfer_page(&self) -> Node<Message> {
+        let transfer = &self.model.transfer;
+        let export = format_puzzle(&self.model.puzzle_givens());
+        let copied = match transfer.copied {
+            Some(true) => span![C!["text-success"], self.tr("export-copied")],
+            Some(false) => span![C!["text-danger"], self.tr("export-copy-failed")],
+            None => seed::empty(),
+        };
+
+        let content = div![
+            C!["alert alert-light"],
+            h1![C!["alert-heading"], self.tr("transfer-header")],
+            h4![self.tr("export-header")],
+            textarea![
+                id!("export-text"),
+                C!["form-control font-monospace mb-2"],
+                attrs! {
+                    At::ReadOnly => true,
+                    At::Rows => self.model.get_size(),
+                    At::Value => export,
+                },
+            ],
+            button![
+                C!["btn btn-outline-secondary"],
+                id!("copy-puzzle"),
+                self.tr("export-copy"),
+                ev(Ev::Click, |_| { Message::CopyPuzzle })
+            ],
+            "\u{a0}",
+            copied,
+            hr![],
+            h4![self.tr("import-header")],
+            p![self.tr("import-help")],
+            textarea![
+                id!("import-text"),
+                C!["form-control font-monospace mb-2"],
+                attrs! {
+                    At::Rows => 8,
+                    At::Value => transfer.import_text,
+                },
+                input_ev(Ev::Input, Message::ImportTextChanged)
+            ],
+            div![
+                C!["mb-2"],
+                label![
+                    C!["form-label"],
+                    attrs! {At::For => "import-file"},
+                    self.tr("import-file")
+                ],
+                input![
+                    id!("import-file"),
+                    C!["form-control"],
+                    attrs! {
+                        At::Type => "file",
+                        At::Accept => ".txt,text/plain",
+                    },
+                    ev(Ev::Change, |event| {
+                        let input: web_sys::HtmlInputElement = event.target()?.dyn_into().ok()?;
+                        let file = input.files()?.get(0)?;
+                        Some(Message::ImportFile(file))
+                    })
+                ],
+            ],
+            transfer.error.map(|error| self.view_import_error(error)),
+            button![
+                C!["btn btn-primary"],
+                id!("import-puzzle"),
+                self.tr("import-puzzle"),
+                ev(Ev::Click, |_| { Message::ImportPuzzle })
+            ],
+            "\u{a0}",
+            button![
+                C!["btn btn-secondary"],
+                id!("close-transfer"),
+                self.tr("close"),
+                ev(Ev::Click, |_| { Message::ToggleTransfer })
+            ],
+        ];
+        self.view_overlay("transfer-page", content)
+    }
+
     fn view_game(&self) -> Vec<Node<Message>> {
         let is_full = is_board_full(&self.model.board);
-        let is_valid = is_board_valid(&self.model.board);
+        // a complete board in the editor is a puzzle, not a won game
+        let is_valid = is_board_valid(&self.model.board) && !self.model.editing;
         nodes![
-            self.view_board("board", is_full && !is_valid),
-            IF!(is_valid => self.view_success_page())
+            div![
+                id!("board-status"),
+                C!["visually-hidden"],
+                attrs! {"role" => "status", At::AriaLive => "polite"},
+                self.announcement(is_full, is_valid)
+            ],
+            self.view_board("board", is_full && !is_valid, /*with_counts:*/ true),
+            IF!(is_valid => self.view_success_page()),
+            IF!(self.model.show_statistics => self.view_statistics_page()),
+            IF!(self.model.transfer.visible => self.view_transfer_page())
+        ]
+    }
+
+    fn view_rating(&self) -> Node<Message> {
+        let text = match self.model.rating {
+            Some(rating) => {
+                let mut args = FluentArgs::new();
+                args.set(
+                    "rating",
+                    FluentValue::from(self.tr(&difficulty_id(rating.difficulty()))),
+                );
+                args.set("steps", FluentValue::from(rating.steps));
+                let technique = match rating.hardest {
+                    Some(technique) => self.tr(&technique_id(technique)),
+                    None => self.tr("technique-none"),
+                };
+                args.set("technique", FluentValue::from(technique));
+                self.tr_with_args("rating-display", Some(&args))
+            }
+            None => self.tr("rating-unsolvable"),
+        };
+        p![id!("Rating-Display"), C!["text-muted"], text]
+    }
+
+    /// Shows the progress of a running puzzle generation with a button to cancel it.
+    fn view_generation(&self) -> Node<Message> {
+        let generation = match &self.model.generation {
+            Some(generation) => generation,
+            None => return seed::empty(),
+        };
+        let mut args = FluentArgs::new();
+        args.set("size", FluentValue::from(generation.size()));
+        args.set("attempt", FluentValue::from(generation.attempts() + 1));
+        div![
+            id!("generation"),
+            C!["alert alert-info"],
+            attrs! {"role" => "status"},
+            span![
+                C!["spinner-border spinner-border-sm"],
+                attrs! {At::AriaHidden => true}
+            ],
+            " ",
+            if self.model.waiting {
+                self.tr_with_args("generation-first", Some(&args))
+            } else {
+                self.tr_with_args("generation-progress", Some(&args))
+            },
+            " ",
+            // without a game to go back to, the generation cannot be cancelled
+            IF!(!self.model.waiting => button![
+                C!["btn btn-sm btn-outline-secondary"],
+                id!("cancel-generation"),
+                self.tr("generation-cancel"),
+                ev(Ev::Click, |_| { Message::CancelGeneration })
+            ]),
         ]
     }
 
     fn view_new_game(&self, difficulty: Difficulty) -> Vec<Node<Message>> {
-        // build arguments for translation difficulty-display
-        let mut difficulty_arg = FluentArgs::new();
-        difficulty_arg.set(
-            "difficulty",
-            FluentValue::from(self.tr(&format!("difficulty-{}", difficulty))),
+        let text = if self.model.editing {
+            let mut size_arg = FluentArgs::new();
+            size_arg.set("size", FluentValue::from(self.model.get_size()));
+            self.tr_with_args("editor-display", Some(&size_arg))
+        } else if let Some(date) = &self.model.daily {
+            let mut date_arg = FluentArgs::new();
+            date_arg.set("date", FluentValue::from(date.as_str()));
+            self.tr_with_args("daily-display", Some(&date_arg))
+        } else if self.model.is_custom {
+            let mut custom_args = FluentArgs::new();
+            custom_args.set("size", FluentValue::from(self.model.get_size()));
+            custom_args.set(
+                "difficulty",
+                FluentValue::from(self.tr(&difficulty_id(difficulty))),
+            );
+            self.tr_with_args("custom-display", Some(&custom_args))
+        } else {
+            // build arguments for translation difficulty-display
+            let mut difficulty_arg = FluentArgs::new();
+            difficulty_arg.set(
+                "difficulty",
+                FluentValue::from(self.tr(&difficulty_id(difficulty))),
+            );
+            self.tr_with_args("difficulty-display", Some(&difficulty_arg))
+        };
+
+        let diff_header = h4![id!("Difficulty-Display"), text];
+
+        let mut time_arg = FluentArgs::new();
+        time_arg.set(
+            "time",
+            FluentValue::from(format_time(
+                self.model.timer.elapsed_ms(self.model.now) as u64
+            )),
         );
+        let time = p![
+            id!("Time-Display"),
+            self.tr_with_args("time-display", Some(&time_arg))
+        ];
 
-        let text = self.tr_with_args("difficulty-display", Some(&difficulty_arg));
+        nodes![
+            diff_header,
+            IF!(!self.model.waiting => self.view_rating()),
+            IF!(!self.model.editing => time),
+            self.view_new_game_button(),
+            self.view_generation(),
+            self.view_custom_game_form()
+        ]
+    }
 
-        let diff_header = h4![id!("Difficulty-Display"), text];
+    /// Returns the name of `language` in that language.
+    fn language_name(&self, language: Language) -> String {
+        let bundle = self.model.res_mgr.get_bundle(language.locale());
+        let mut errors = vec![];
+        bundle
+            .get_message("language-name")
+            .and_then(|msg| msg.value())
+            .map(|value| bundle.format_pattern(value, None, &mut errors).to_string())
+            .unwrap_or_else(|| language.to_string())
+    }
 
-        nodes![diff_header, self.view_new_game_button()]
+    fn view_language_picker(&self) -> Node<Message> {
+        let items = Language::all().map(|language| {
+            // a button, as a link to "#" would drop the shared puzzle from the URL
+            li![button![
+                C![
+                    "dropdown-item",
+                    IF!(language == self.model.language => "active")
+                ],
+                attrs! {
+                    At::Type => "button",
+                    At::Lang => language.locale(),
+                },
+                self.language_name(language),
+                ev(Ev::Click, move |_| { Message::SetLanguage(language) })
+            ]]
+        });
+        div![
+            C!["language-switch dropdown"],
+            button![
+                id!("language-picker"),
+                C!["btn btn-link dropdown-toggle"],
+                attrs! {
+                    "data-bs-toggle" => "dropdown",
+                    "aria-expanded" => false,
+                    At::Title => self.tr("language-picker"),
+                },
+                img![attrs! {
+                    At::Src => "translate.svg",
+                    At::Alt => self.tr("language-picker"),
+                    At::Height => "48",
+                    At::Width => "48",
+                }],
+            ],
+            ul![
+                C!["dropdown-menu dropdown-menu-end"],
+                attrs! {At::AriaLabelledBy => "language-picker"},
+                items,
+            ]
+        ]
     }
 
     fn view_footer(&self) -> Node<Message> {
@@ -251,7 +1078,59 @@ impl<'a> ViewBuilder<'a> {
                     " | {}: {}",
                     self.tr("version"),
                     VERSION.unwrap_or(&self.tr("version-unknown"))
-                )
+                ),
+                self.view_diagnostics_toggle(),
+            ]
+        ]
+    }
+
+    /// Toggles the diagnostics panel, only shown if a translation failed.
+    fn view_diagnostics_toggle(&self) -> Node<Message> {
+        let count = self.model.res_mgr.diagnostics().len();
+        if count == 0 {
+            return seed::empty();
+        }
+        let mut args = FluentArgs::new();
+        args.set("count", FluentValue::from(count));
+        span![
+            " | ",
+            button![
+                id!("toggle-diagnostics"),
+                C!["btn btn-link"],
+                attrs! {At::Type => "button"},
+                self.tr_with_args("diagnostics", Some(&args)),
+                ev(Ev::Click, |_| { Message::ToggleDiagnostics })
+            ]
+        ]
+    }
+
+    /// Lists the translation problems for translators, rendered last to include
+    /// the problems of the current view.
+    fn view_diagnostics(&self) -> Node<Message> {
+        if !self.model.show_diagnostics {
+            return seed::empty();
+        }
+        let rows = self
+            .model
+            .res_mgr
+            .diagnostics()
+            .into_iter()
+            .map(|d| tr![td![d.locale], td![code![d.id]], td![d.problem],]);
+        div![
+            C!["row"],
+            div![
+                C!["col"],
+                id!("diagnostics"),
+                h4![self.tr("diagnostics-header")],
+                table![
+                    C!["table table-sm"],
+                    thead![tr![
+                        th![self.tr("diagnostics-locale")],
+                        th![self.tr("diagnostics-id")],
+                        th![self.tr("diagnostics-problem")],
+                    ]],
+                    tbody![rows],
+                ]
             ]
         ]
     }
@@ -261,20 +1140,7 @@ impl<'a> ViewBuilder<'a> {
             C!["row"],
             div![
                 C!["col"],
-                div![
-                    C!["language-switch"],
-                    attrs! {
-                        "data-toggle" => "tooltip";
-                        "data-placement" => "bottom";
-                        At::Title => self.tr("language-toggle");
-                    },
-                    img![attrs! {
-                        At::Src => "translate.svg",
-                        At::Height => "300%",
-                        At::Width => "300%",
-                    }],
-                    ev(Ev::Click, |_| { Message::ToggleLanguage }),
-                ],
+                self.view_language_picker(),
                 h1![self.tr("header")],
             ]
         ];
@@ -287,27 +1153,115 @@ impl<'a> ViewBuilder<'a> {
                 self.tr("clear-board"),
                 ev("click", |_| { Message::Clear })
             ],
+            "\u{a0}",
+            self.view_history_buttons(),
+            "\u{a0}",
+            self.view_note_mode_button(),
+            "\u{a0}",
+            button![
+                C!["btn btn-outline-info"],
+                id!("show-hint"),
+                self.tr("hint"),
+                ev(Ev::Click, |_| { Message::Hint })
+            ],
+            "\u{a0}",
+            IF!(!self.model.editing => button![
+                C!["btn btn-outline-info"],
+                id!("check-board"),
+                attrs! {At::Title => self.tr("check-tooltip")},
+                self.tr("check"),
+                ev(Ev::Click, |_| { Message::Check })
+            ]),
+            self.view_hint(),
+            self.view_check(),
+            self.view_reveal_buttons(),
+            self.view_editor(),
+            self.view_ambiguous_warning(),
             self.view_new_game(self.model.difficulty),
+            button![
+                C!["btn btn-outline-secondary"],
+                id!("share-progress"),
+                attrs! {
+                    "data-toggle" => "tooltip";
+                    "data-placement" => "right";
+                    At::Title => self.tr("share-progress-tooltip");
+                },
+                self.tr("share-progress"),
+                ev(Ev::Click, |_| { Message::ShareProgress })
+            ],
+            "\u{a0}",
+            button![
+                C!["btn btn-outline-secondary"],
+                id!("show-statistics"),
+                self.tr("statistics"),
+                ev(Ev::Click, |_| { Message::ToggleStatistics })
+            ],
+            "\u{a0}",
+            button![
+                C!["btn btn-outline-secondary"],
+                id!("show-transfer"),
+                self.tr("transfer"),
+                ev(Ev::Click, |_| { Message::ToggleTransfer })
+            ],
             h4![self.tr("rules-header")],
             ul![
                 li![self.tr("rule-1")],
                 li![self.tr("rule-2")],
                 li![self.tr("rule-3")],
-            ]
+            ],
+            p![C!["text-muted"], self.tr("keyboard-help")]
         ];
+        let footer = self.view_footer();
         div![
             C!["container"],
             header,
             div![C!["row"], board, controls],
-            self.view_footer()
+            footer,
+            self.view_diagnostics()
         ]
     }
 }
 
+/// Parses the inline SVG icon of `field`, which needs the browser.
+fn svg_icon(field: Field) -> Vec<Node<Message>> {
+    match field {
+        Field::Empty => {
+            Node::<Message>::from_html(None, include_str!("../assets/icons/dot-opt.svg"))
+        }
+        Field::X => Node::<Message>::from_html(None, include_str!("../assets/icons/x-opt.svg")),
+        Field::O => Node::<Message>::from_html(None, include_str!("../assets/icons/o-opt.svg")),
+    }
+}
+
+/// Returns the id of the translated name of `difficulty`.
+fn difficulty_id(difficulty: Difficulty) -> String {
+    format!("difficulty-{}", difficulty)
+}
+
+/// Returns the id of the translated name of `field`, read by screen readers.
+fn field_id(field: Field) -> String {
+    format!("field-{:?}", field)
+}
+
+/// Returns the id of the translated name of `technique`.
+fn technique_id(technique: Technique) -> String {
+    format!("technique-{:?}", technique)
+}
+
+/// Returns the conflicts to highlight, none if the helper is disabled.
+fn conflicts(model: &Model) -> Conflicts {
+    match model.helper {
+        Helper::Enabled => Conflicts::of(&model.board),
+        Helper::Disabled => Conflicts::none(model.get_size()),
+    }
+}
+
 fn build_view(model: &Model) -> ViewBuilder {
     ViewBuilder {
         bundle: model.res_mgr.get_bundle(&model.language.to_string()),
         model,
+        icon: svg_icon,
+        conflicts: conflicts(model),
     }
 }
 
@@ -315,3 +1269,503 @@ pub fn view(model: &Model) -> Node<Message> {
     let vb = build_view(model);
     vb.view()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::lang::{FALLBACK_LOCALE, LANGUAGES};
+    use crate::solver::Step;
+    use binoxxo::field::Board;
+    use fluent_syntax::ast;
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    fn model(givens: &str, board: &str) -> Model {
+        Model::with_board(
+            Difficulty::Easy,
+            Helper::Enabled,
+            Language::default(),
+            &Board::from_str(givens).unwrap(),
+            Board::from_str(board).unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// Renders fields as text, as parsing the SVG icons needs the browser.
+    fn test_view(model: &Model) -> ViewBuilder<'_> {
+        ViewBuilder {
+            icon: |field| vec![Node::new_text(format!("{:?}", field))],
+            ..build_view(model)
+        }
+    }
+
+    fn attr(node: &Node<Message>, at: &'static str) -> Option<String> {
+        match node {
+            Node::Element(el) => match el.attrs.vals.get(&At::from(at)) {
+                Some(AtValue::Some(value)) => Some(value.replace(['\u{2068}', '\u{2069}'], "")),
+                Some(AtValue::None) => Some(String::new()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns all elements below `node` with the ARIA `role`.
+    fn find_role<'a>(node: &'a Node<Message>, role: &str, found: &mut Vec<&'a Node<Message>>) {
+        if attr(node, "role").as_deref() == Some(role) {
+            found.push(node);
+        }
+        if let Node::Element(el) = node {
+            for child in el.children.iter() {
+                find_role(child, role, found);
+            }
+        }
+    }
+
+    /// Returns the text below `node` without the bidi isolation marks inserted by fluent.
+    fn text(node: &Node<Message>) -> String {
+        match node {
+            Node::Text(text) => text.text.replace(['\u{2068}', '\u{2069}'], ""),
+            Node::Element(el) => el.children.iter().map(text).collect(),
+            _ => String::new(),
+        }
+    }
+
+    const GIVENS: &str = "
+        X _ _ _ _ _
+        _ _ _ _ _ _
+        _ _ _ _ _ _
+        _ _ _ _ _ _
+        _ _ _ _ _ _
+        _ _ _ _ _ _";
+
+    #[test]
+    fn board_is_a_labelled_grid() {
+        let model = model(
+            GIVENS,
+            "
+            X _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ O _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _",
+        );
+        let board = test_view(&model).view_board("board", false, true);
+
+        let mut grids = Vec::new();
+        find_role(&board, "grid", &mut grids);
+        assert_eq!(1, grids.len());
+        assert_eq!(Some("6".to_string()), attr(grids[0], "aria-rowcount"));
+
+        let mut rows = Vec::new();
+        find_role(&board, "row", &mut rows);
+        assert_eq!(6, rows.len());
+
+        let mut cells = Vec::new();
+        find_role(&board, "gridcell", &mut cells);
+        assert_eq!(36, cells.len());
+        assert_eq!(
+            Some("row 1, column 1, X, given".to_string()),
+            attr(cells[0], "aria-label")
+        );
+        assert_eq!(Some("true".to_string()), attr(cells[0], "aria-readonly"));
+        assert_eq!(
+            Some("row 3, column 3, O, guess".to_string()),
+            attr(cells[2 * 6 + 2], "aria-label")
+        );
+        assert_eq!(
+            Some("false".to_string()),
+            attr(cells[2 * 6 + 2], "aria-readonly")
+        );
+    }
+
+    #[test]
+    fn generation_shows_progress() {
+        let mut model = model(GIVENS, GIVENS);
+        assert!(matches!(test_view(&model).view_generation(), Node::Empty));
+        model.generation = Some(Generation::new(Difficulty::Hard));
+        let status = test_view(&model).view_generation();
+        assert_eq!(Some("status".to_string()), attr(&status, "role"));
+        assert!(text(&status).contains("10×10 puzzle (attempt 1)"));
+        assert!(text(&status).ends_with("Cancel"));
+
+        // the first puzzle has no game to go back to
+        let mut model = Model::waiting(Difficulty::Easy, Helper::Enabled, Language::default());
+        model.generation = Some(Generation::new(Difficulty::Easy));
+        let view = test_view(&model);
+        let status = view.view_generation();
+        assert_eq!("Generating a 6×6 puzzle (attempt 1)…", text(&status).trim());
+        assert!(matches!(view.view_ambiguous_warning(), Node::Empty));
+    }
+
+    #[test]
+    fn translation_problems_are_reported() {
+        let model = model(GIVENS, GIVENS);
+        let vb = test_view(&model);
+        assert_eq!("no-such-message", vb.tr("no-such-message"));
+        // the count is not supplied
+        assert!(vb.tr("check-wrong").contains("{$count}"));
+        vb.tr("no-such-message");
+
+        let diagnostics = model.res_mgr.diagnostics();
+        let ids: Vec<&str> = diagnostics.iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(vec!["no-such-message", "check-wrong"], ids);
+        assert_eq!("en-US", diagnostics[0].locale);
+    }
+
+    #[test]
+    fn marks_replace_the_dot() {
+        let mut model = model(GIVENS, GIVENS);
+        model.notes.set(1, 0, Marks { x: true, o: true });
+        model.notes.set(2, 0, Marks { x: false, o: true });
+        let board = test_view(&model).view_board("board", false, true);
+        let mut cells = Vec::new();
+        find_role(&board, "gridcell", &mut cells);
+        assert_eq!("XO", text(cells[1]));
+        assert_eq!("O", text(cells[2]));
+        assert_eq!("Empty", text(cells[3]));
+    }
+
+    #[test]
+    fn wrong_fields_are_flagged() {
+        let solved = "
+            X O O X
+            O X X O
+            X X O O
+            O O X X";
+        let empty = "
+            _ _ _ _
+            _ _ _ _
+            _ _ _ _
+            _ _ _ _";
+        // the X in the third column breaks no rule, but is wrong
+        let mut model = model(empty, empty);
+        model.board.set(0, 0, Field::X);
+        model.board.set(2, 0, Field::X);
+        model.check = CheckState::of(&model.board, &Board::from_str(solved).unwrap());
+        model.mistakes = 1;
+        let view = test_view(&model);
+        let board = view.view_board("board", false, true);
+        let mut cells = Vec::new();
+        find_role(&board, "gridcell", &mut cells);
+        assert!(attr(cells[2], "class").unwrap().contains("wrong"));
+        assert_eq!(
+            Some("This field differs from the solution.".to_string()),
+            attr(cells[2], "title")
+        );
+        assert_eq!("One field is wrong.Mistakes: 1", text(&view.view_check()));
+    }
+
+    #[test]
+    fn revealed_fields_are_marked() {
+        let mut model = model(GIVENS, GIVENS);
+        model.board.set(1, 0, Field::O);
+        model.revealed.push(CellPos { col: 1, row: 0 });
+        let board = test_view(&model).view_board("board", false, true);
+        let mut cells = Vec::new();
+        find_role(&board, "gridcell", &mut cells);
+        assert!(attr(cells[1], "class").unwrap().contains("revealed"));
+        assert_eq!(
+            Some("row 1, column 2, O, revealed".to_string()),
+            attr(cells[1], "aria-label")
+        );
+    }
+
+    #[test]
+    fn clicked_field_is_selected_without_cursor() {
+        let mut model = model(GIVENS, GIVENS);
+        model.selected = Some(CellPos { col: 1, row: 0 });
+        let board = test_view(&model).view_board("board", false, true);
+        let mut cells = Vec::new();
+        find_role(&board, "gridcell", &mut cells);
+        let class = attr(cells[1], "class").unwrap();
+        assert!(class.contains("selected"));
+        assert!(!class.contains("cursor"));
+        assert_eq!(Some("true".to_string()), attr(cells[1], "aria-selected"));
+        assert_eq!(Some("false".to_string()), attr(cells[2], "aria-selected"));
+
+        model.cursor = model.selected;
+        let board = test_view(&model).view_board("board", false, true);
+        let mut cells = Vec::new();
+        find_role(&board, "gridcell", &mut cells);
+        let class = attr(cells[1], "class").unwrap();
+        assert!(class.contains("cursor"));
+        assert!(!class.contains("selected"));
+    }
+
+    #[test]
+    fn lines_are_counted() {
+        let model = model(
+            GIVENS,
+            "
+            X O X _ _ _
+            X _ _ _ _ _
+            O _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _",
+        );
+        let board = test_view(&model).view_board("board", false, true);
+        let mut rows = Vec::new();
+        find_role(&board, "row", &mut rows);
+        let Node::Element(first_row) = rows[0] else {
+            panic!("row is no element")
+        };
+        let count = first_row.children.last().unwrap();
+        assert_eq!("2X 1O", text(count));
+        assert_eq!(Some("true".to_string()), attr(count, "aria-hidden"));
+
+        let mut all = Vec::new();
+        find_role(&board, "grid", &mut all);
+        let Node::Element(grid) = all[0] else {
+            panic!("grid is no element")
+        };
+        let Node::Element(column_counts) = grid.children.last().unwrap() else {
+            panic!("counts are no element")
+        };
+        assert_eq!("2X 1O", text(&column_counts.children[0]));
+        assert_eq!("", text(column_counts.children.last().unwrap()));
+    }
+
+    #[test]
+    fn errors_are_labelled_and_announced() {
+        let model = model(
+            GIVENS,
+            "
+            X X X _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _",
+        );
+        let view = test_view(&model);
+        let board = view.view_board("board", false, true);
+        let mut cells = Vec::new();
+        find_role(&board, "gridcell", &mut cells);
+        assert_eq!(
+            Some("row 1, column 3, X, guess, error".to_string()),
+            attr(cells[2], "aria-label")
+        );
+        assert_eq!(Some("true".to_string()), attr(cells[2], "aria-invalid"));
+        assert!(attr(cells[2], "class").unwrap().contains("triple"));
+        assert_eq!(
+            Some("Three equal symbols are next to each other.".to_string()),
+            attr(cells[2], "title")
+        );
+        assert_eq!(None, attr(cells[3], "title"));
+
+        let game = view.view_game();
+        let status = game
+            .iter()
+            .find(|node| attr(node, "role").as_deref() == Some("status"))
+            .unwrap();
+        assert_eq!(Some("polite".to_string()), attr(status, "aria-live"));
+        assert_eq!("3 fields break a rule.", text(status));
+    }
+
+    #[test]
+    fn victory_is_announced() {
+        let solved = "
+            X O X O
+            O X O X
+            X X O O
+            O O X X";
+        let model = model(solved, solved);
+        let game = test_view(&model).view_game();
+        let status = game
+            .iter()
+            .find(|node| attr(node, "role").as_deref() == Some("status"))
+            .unwrap();
+        assert_eq!("Solved! Congratulations.", text(status));
+    }
+
+    const TECHNIQUES: [Technique; 5] = [
+        Technique::Pair,
+        Technique::NoTriple,
+        Technique::Balance,
+        Technique::Uniqueness,
+        Technique::LineAnalysis,
+    ];
+
+    /// Returns models in every state showing different messages.
+    fn states() -> Vec<Model> {
+        let mut states = Vec::new();
+        let mut add = |givens: &str, board: &str, change: &dyn Fn(&mut Model)| {
+            let mut model = model(givens, board);
+            change(&mut model);
+            states.push(model);
+        };
+        // breaks every rule: triple and imbalance in the first row, duplicate last rows
+        let broken = "
+            X X X X _ _
+            O _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            X O X O X O
+            X O X O X O";
+        let solved = "
+            X O X O
+            O X O X
+            X X O O
+            O O X X";
+        let duplicated = "
+            X X O O
+            O O X X
+            X X O O
+            O O X X";
+        let selected = CellPos { col: 1, row: 1 };
+
+        add(GIVENS, broken, &|model| {
+            model.revealed = vec![selected];
+            model.selected = Some(selected);
+            model.check = CheckState::Wrong(vec![(CellPos { col: 0, row: 1 }, Field::O)]);
+            model.note_mode = true;
+        });
+        add(GIVENS, GIVENS, &|model| model.helper = Helper::Disabled);
+        for technique in TECHNIQUES {
+            add(GIVENS, GIVENS, &|model| {
+                model.hint = HintState::Found(Step {
+                    col: 1,
+                    row: 0,
+                    field: Field::O,
+                    technique,
+                })
+            });
+        }
+        add(GIVENS, GIVENS, &|model| model.hint = HintState::NotFound);
+        add(GIVENS, GIVENS, &|model| model.check = CheckState::Correct);
+        add(GIVENS, GIVENS, &|model| {
+            model.check = CheckState::Unavailable
+        });
+        add(GIVENS, GIVENS, &|model| {
+            model.pending_reveal = Some(Reveal::Cell(selected))
+        });
+        add(GIVENS, GIVENS, &|model| {
+            model.pending_reveal = Some(Reveal::Solution)
+        });
+        for count in [
+            SolutionCount::NoSolution,
+            SolutionCount::Unique,
+            SolutionCount::Multiple,
+        ] {
+            add(GIVENS, GIVENS, &|model| {
+                model.editing = true;
+                model.solution_count = count;
+            });
+        }
+        add(GIVENS, GIVENS, &|model| {
+            model.daily = Some("2026-10-18".to_string());
+            model.rating = None;
+        });
+        add(GIVENS, GIVENS, &|model| {
+            model.is_custom = true;
+            model.show_custom = true;
+        });
+        add(GIVENS, GIVENS, &|model| {
+            model.generation = Some(Generation::new(Difficulty::Hard))
+        });
+        add(GIVENS, GIVENS, &|model| {
+            model.generation = Some(Generation::new(Difficulty::Hard));
+            model.waiting = true;
+        });
+        add(GIVENS, GIVENS, &|model| {
+            model.show_statistics = true;
+            model.game_log.log_start(Difficulty::Easy, 6);
+            model.game_log.log_win(Difficulty::Easy, 6, 60_000, false);
+        });
+        for error in [
+            ImportError::Empty,
+            ImportError::InvalidChar {
+                ch: '?',
+                row: 1,
+                col: 2,
+            },
+            ImportError::RowLength {
+                row: 1,
+                length: 3,
+                size: 4,
+            },
+            ImportError::InvalidSize(3),
+            ImportError::BreaksRules,
+            ImportError::NoSolution,
+            ImportError::Ambiguous,
+        ] {
+            add(GIVENS, GIVENS, &|model| {
+                model.transfer.visible = true;
+                model.transfer.error = Some(error);
+            });
+        }
+        for copied in [true, false] {
+            add(GIVENS, GIVENS, &|model| {
+                model.transfer.visible = true;
+                model.transfer.copied = Some(copied);
+            });
+        }
+        add(solved, solved, &|model| {
+            model.result = Some(GameResult {
+                time_ms: 60_000,
+                new_record: true,
+            });
+            model.records.add(Difficulty::Easy, 60_000);
+            model.revealed = vec![selected];
+        });
+        add(duplicated, duplicated, &|_| ());
+        add(GIVENS, GIVENS, &|model| {
+            model.show_diagnostics = true;
+            model.res_mgr.report(Diagnostic {
+                locale: "de-DE".to_string(),
+                id: "missing".to_string(),
+                problem: "message is missing".to_string(),
+            });
+        });
+        states
+    }
+
+    #[test]
+    fn every_message_is_translated() {
+        for language in Language::all() {
+            for mut model in states() {
+                model.language = language;
+                let before = model.res_mgr.diagnostics();
+                test_view(&model).view();
+                assert_eq!(before, model.res_mgr.diagnostics(), "in {}", language);
+            }
+        }
+    }
+
+    #[test]
+    fn every_message_is_used() {
+        // the names of the languages are read from their own bundles
+        let mut used = HashSet::from(["language-name".to_string()]);
+        // the puzzles of the states are rated without the name of a technique
+        used.extend(TECHNIQUES.map(technique_id));
+        used.extend([Field::X, Field::O, Field::Empty].map(field_id));
+        used.extend([Difficulty::Easy, Difficulty::Medium, Difficulty::Hard].map(difficulty_id));
+        for model in states() {
+            // every message requested from an empty bundle is reported as missing
+            ViewBuilder {
+                bundle: FluentBundle::new(vec![]),
+                ..test_view(&model)
+            }
+            .view();
+            used.extend(model.res_mgr.diagnostics().into_iter().map(|d| d.id));
+        }
+
+        let (_, ftl) = LANGUAGES
+            .iter()
+            .find(|(locale, _)| *locale == FALLBACK_LOCALE)
+            .unwrap();
+        let resource = FluentResource::try_new(ftl.to_string()).unwrap();
+        for entry in resource.entries() {
+            if let ast::Entry::Message(message) = entry {
+                assert!(
+                    used.contains(message.id.name),
+                    "{} is never used",
+                    message.id.name
+                );
+            }
+        }
+    }
+}